@@ -0,0 +1,190 @@
+use crate::isolation::catch_panic;
+use crate::quests::Quest;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BenchLimit {
+    Iterations(usize),
+    TimeBudget(Duration),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    pub warmup: usize,
+    pub limit: BenchLimit,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            limit: BenchLimit::TimeBudget(Duration::from_secs(3)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub std_dev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let count = sorted.len();
+        let nanos = sorted
+            .iter()
+            .map(|d| d.as_nanos() as f64)
+            .collect::<Vec<_>>();
+        let mean = nanos.iter().sum::<f64>() / count as f64;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / count as f64;
+        let median = if count.is_multiple_of(2) {
+            (sorted[count / 2 - 1] + sorted[count / 2]) / 2
+        } else {
+            sorted[count / 2]
+        };
+        // nearest-rank percentile
+        let p95_rank = (0.95 * count as f64).ceil() as usize;
+        Some(Self {
+            samples: count,
+            min: sorted[0],
+            median,
+            mean: Duration::from_nanos(mean.round() as u64),
+            p95: sorted[p95_rank.max(1) - 1],
+            std_dev: Duration::from_nanos(variance.sqrt().round() as u64),
+        })
+    }
+}
+
+pub fn measure<F: FnMut()>(config: &BenchConfig, mut f: F) -> Option<Stats> {
+    for _ in 0..config.warmup {
+        f();
+    }
+    let mut samples = Vec::new();
+    let start = Instant::now();
+    loop {
+        let done = match config.limit {
+            BenchLimit::Iterations(count) => samples.len() >= count,
+            BenchLimit::TimeBudget(budget) => !samples.is_empty() && start.elapsed() >= budget,
+        };
+        if done {
+            break;
+        }
+        let now = Instant::now();
+        f();
+        samples.push(now.elapsed());
+    }
+    Stats::from_samples(&samples)
+}
+
+pub fn bench(quest: &Quest, only_part: Option<usize>, config: &BenchConfig) -> Vec<(usize, Stats)> {
+    let mut results = Vec::new();
+    println!();
+    println!("{}", quest.info.title());
+    let solution = &quest.solution;
+    for declared in solution.parts() {
+        if only_part.is_some_and(|p| p != declared.number) {
+            continue;
+        }
+        let part = declared.index();
+        let input = match quest.input_loader.load(declared) {
+            Ok(input) => input,
+            Err(err) => {
+                println!("[Error] {}", err);
+                continue;
+            }
+        };
+        // the part is verified once to skip failing or panicking solutions
+        match catch_panic(|| solution.solve(part, &input)) {
+            Ok(Ok(_)) => {}
+            Ok(Err(err)) => {
+                println!("[Error] part {} failed: {}", part + 1, err);
+                continue;
+            }
+            Err(report) => {
+                println!("[Error] part {} {}", part + 1, report);
+                continue;
+            }
+        }
+        let stats = measure(config, || {
+            black_box(solution.solve(part, &input)).ok();
+        });
+        let Some(stats) = stats else {
+            println!("[Warn] no samples collected for part {}", part + 1);
+            continue;
+        };
+        println!(
+            "part {}: {} runs, min {}, median {}, mean {}, p95 {}, std dev {}",
+            part + 1,
+            stats.samples,
+            format_precise(stats.min),
+            format_precise(stats.median),
+            format_precise(stats.mean),
+            format_precise(stats.p95),
+            format_precise(stats.std_dev),
+        );
+        results.push((part + 1, stats));
+    }
+    results
+}
+
+pub fn format_precise(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{} ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.2} µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2} ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2} s", nanos as f64 / 1e9)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bench_stats() {
+        let samples = [5, 1, 4, 2, 3].map(Duration::from_micros);
+        let stats = Stats::from_samples(&samples).unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_micros(3));
+        assert_eq!(stats.mean, Duration::from_micros(3));
+        assert_eq!(stats.p95, Duration::from_micros(5));
+        assert_eq!(stats.std_dev, Duration::from_nanos(1414));
+        assert!(Stats::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn bench_measure_iterations() {
+        let mut calls = 0;
+        let config = BenchConfig {
+            warmup: 2,
+            limit: BenchLimit::Iterations(10),
+        };
+        let stats = measure(&config, || calls += 1).unwrap();
+        assert_eq!(stats.samples, 10);
+        assert_eq!(calls, 12);
+    }
+
+    #[test]
+    fn bench_format_precise() {
+        assert_eq!(format_precise(Duration::from_nanos(512)), "512 ns");
+        assert_eq!(format_precise(Duration::from_nanos(1_500)), "1.50 µs");
+        assert_eq!(format_precise(Duration::from_micros(2_250)), "2.25 ms");
+        assert_eq!(format_precise(Duration::from_millis(3_100)), "3.10 s");
+    }
+}
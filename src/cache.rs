@@ -0,0 +1,178 @@
+use crate::quests::{Answer, Event};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const CACHE_FILE: &str = "answer_cache.tsv";
+
+const HEADER: &str = "event\tquest\tpart\tkey\tduration_ns\tanswer";
+
+/// Cached answer of a part, valid while the key matches
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub key: u64,
+    pub answer: String,
+    /// Parse and solve time of the run that produced the answer
+    pub duration: Duration,
+}
+
+pub type PartKey = (Event, usize, usize);
+
+/// Answers of the last runs, one per part
+#[derive(Debug, Default)]
+pub struct AnswerCache {
+    entries: HashMap<PartKey, Entry>,
+}
+
+impl AnswerCache {
+    /// Loads the cache file, a missing or unreadable file gives an empty cache
+    pub fn load<P: AsRef<Path>>(path: P) -> Self {
+        let content = std::fs::read_to_string(path).unwrap_or_default();
+        let entries = content
+            .lines()
+            .filter(|line| !line.is_empty() && *line != HEADER)
+            .filter_map(parse_line)
+            .collect();
+        Self { entries }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let mut keys = self.entries.keys().collect::<Vec<_>>();
+        keys.sort();
+        let mut content = String::from(HEADER);
+        for key in keys {
+            let (event, quest, part) = key;
+            let entry = &self.entries[key];
+            content.push_str(&format!(
+                "\n{}\t{}\t{}\t{:016x}\t{}\t{}",
+                event,
+                quest,
+                part,
+                entry.key,
+                entry.duration.as_nanos(),
+                entry.answer
+            ));
+        }
+        content.push('\n');
+        std::fs::write(path, content)
+    }
+
+    /// Cached entry of the part if it was produced for the same key
+    pub fn get(&self, part: PartKey, key: u64) -> Option<&Entry> {
+        self.entries.get(&part).filter(|entry| entry.key == key)
+    }
+
+    /// Stores the answer, answers that don't fit in a single line aren't cached
+    pub fn insert(&mut self, part: PartKey, entry: Entry) {
+        if entry.answer.contains(['\t', '\n', '\r']) {
+            return;
+        }
+        self.entries.insert(part, entry);
+    }
+}
+
+/// The cache file is kept in the crate root, so runs from any directory of the checkout share it
+pub fn cache_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(CACHE_FILE)
+}
+
+fn parse_line(line: &str) -> Option<(PartKey, Entry)> {
+    let [event, quest, part, key, duration, answer] = line.splitn(6, '\t').collect::<Vec<_>>()[..]
+    else {
+        return None;
+    };
+    let part = (event.parse().ok()?, quest.parse().ok()?, part.parse().ok()?);
+    let entry = Entry {
+        key: u64::from_str_radix(key, 16).ok()?,
+        answer: answer.to_string(),
+        duration: Duration::from_nanos(duration.parse().ok()?),
+    };
+    Some((part, entry))
+}
+
+/// Key of the input solved by the quest module with the source compiled into the binary.
+/// Changes of code shared with other quests, e.g. `common`, aren't detected and need `--no-cache`
+pub fn input_key(source: &str, input: &str) -> u64 {
+    fnv1a(&[source.as_bytes(), input.as_bytes()])
+}
+
+/// 64-bit FNV-1a hash of the concatenated chunks
+fn fnv1a(chunks: &[&[u8]]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    chunks
+        .iter()
+        .flat_map(|chunk| chunk.iter())
+        .fold(OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+        })
+}
+
+/// Restores the answer type from its text
+pub fn restore_answer(text: &str) -> Answer {
+    if let Ok(value) = text.parse::<i64>() {
+        return Answer::Int(value);
+    }
+    if let Ok(value) = text.parse::<i128>() {
+        return Answer::BigInt(value);
+    }
+    Answer::Text(text.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cache_fnv1a() {
+        assert_eq!(fnv1a(&[]), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(&[b"a"]), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a(&[b"foo", b"bar"]), fnv1a(&[b"foobar"]));
+    }
+
+    #[test]
+    fn cache_input_key() {
+        let key = input_key("fn solve() {}", "ab\ncd");
+        assert_eq!(input_key("fn solve() {}", "ab\ncd"), key);
+        assert_ne!(input_key("fn solve() {}", "ab\ncde"), key);
+        assert_ne!(input_key("fn solve() { 1 }", "ab\ncd"), key);
+    }
+
+    #[test]
+    fn cache_save_load() {
+        let path = std::env::temp_dir().join("koa_cache_save_load.tsv");
+        let part = (Event::Year(2024), 7, 3);
+        let entry = Entry {
+            key: 0xabc,
+            answer: "BEAFIKCHJ".to_string(),
+            duration: Duration::from_nanos(1500),
+        };
+        let mut cache = AnswerCache::default();
+        cache.insert(part, entry.clone());
+        cache.insert(
+            (Event::Story(1), 1, 1),
+            Entry {
+                answer: "multi\nline".to_string(),
+                ..entry.clone()
+            },
+        );
+        cache.save(&path).unwrap();
+
+        let cache = AnswerCache::load(&path);
+        assert_eq!(cache.get(part, 0xabc), Some(&entry));
+        // changed input invalidates the answer
+        assert_eq!(cache.get(part, 0xabd), None);
+        assert_eq!(cache.get((Event::Story(1), 1, 1), 0xabc), None);
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn cache_restore_answer() {
+        assert_eq!(restore_answer("42"), Answer::Int(42));
+        assert_eq!(
+            restore_answer("80131003100210000000"),
+            Answer::BigInt(80131003100210000000)
+        );
+        assert_eq!(restore_answer("RB@"), Answer::Text("RB@".to_string()));
+    }
+}
@@ -0,0 +1,957 @@
+use crate::bench::{BenchConfig, BenchLimit};
+use crate::export::Format;
+use crate::pool;
+use crate::quests::{Event, InputOptions, InputSource};
+use crate::runner::RunConfig;
+use crate::watch;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub const USAGE: &str = "Usage: kingdom_of_algorithmia <command>
+
+Commands:
+  run <event> <quest> [options]   solve all parts of a quest
+  run --latest [options]          solve the last quest of the last event
+  run --latest-solved [options]   solve the last quest with recorded answers
+  bench <event> <quest> [options] measure the solution performance
+  bench-compare [options]         compare the latest benchmarks with a baseline
+  all [options]                   solve every registered quest and print a summary
+  watch <event> <quest> [options] solve a quest again whenever its input files change
+  repl [options]                  explore quests and solve pasted inputs interactively
+  list [--tag TAG]                print registered quests, optionally with the tag
+  new <event> <quest> <title>     generate and register a quest module with empty inputs
+  help                            show this screen
+
+Options:
+  -h, --help                      show this screen
+  -v, --verbose                   show debug messages of solutions, twice for trace
+  -q, --quiet                     show only warnings, twice for errors only
+
+Log messages can be filtered per module with KOA_LOG, e.g.
+KOA_LOG=quests::quest_2024_07=trace,warn
+
+Events are identified by year (2024) or story number (s1).
+
+Run options:
+  --part N                        solve only part N
+  --timeout MS                    cancel parts running longer than MS milliseconds
+                                  (also available for 'all')
+  --examples                      check the puzzle examples before the real input
+                                  (also available for 'all')
+  --tag TAG                       solve only quests with the tag (only for 'all')
+  --jobs N                        solve independent quests and parts on N threads,
+                                  'auto' uses all cores (default: 1, also for 'all')
+  --format FORMAT                 print text, json or csv records of the parts,
+                                  progress goes to stderr (also for 'all')
+  --cache                         serve answers of unchanged inputs and solutions from
+                                  the answer cache (or set KOA_CACHE=1, also for 'all')
+  --no-cache                      solve every part even if KOA_CACHE is set
+
+Watch options:
+  --part N                        watch only part N
+  --timeout MS                    cancel parts running longer than MS milliseconds
+  --interval MS                   polling interval of the input files (default: 500)
+
+Repl options:
+  --timeout MS                    cancel parts running longer than MS milliseconds
+
+Input options (run, bench, all, watch, repl):
+  --input-dir DIR                 directory with input files (default: input,
+                                  or KOA_INPUT_DIR environment variable)
+  --input N=PATH                  read input of part N from PATH, '-' means stdin
+                                  (not available for 'all' and 'repl')
+
+Bench options:
+  --part N                        benchmark only part N
+  --latest                        benchmark the last quest of the last event
+  --warmup N                      untimed runs before measuring (default: 3)
+  --iterations N                  number of timed runs
+  --time-budget MS                time limit for timed runs (default: 3000)
+  --label LABEL                   history label (default: current git revision)
+  --no-record                     don't append results to the benchmark history
+
+Bench compare options:
+  --baseline LABEL                compare with records of the label
+                                  (default: the previous label of each part)
+  --threshold PCT                 report slowdown above PCT percent (default: 10)
+
+Examples:
+  kingdom_of_algorithmia run 2024 7
+  kingdom_of_algorithmia run 2024 5 --part 2
+  kingdom_of_algorithmia run 2024 6 --examples
+  kingdom_of_algorithmia all --jobs auto
+  kingdom_of_algorithmia all --format csv > results.csv
+  kingdom_of_algorithmia run --latest
+  kingdom_of_algorithmia watch 2024 7 --input 1=sample.txt
+  kingdom_of_algorithmia run 2024 4 --input 1=sample.txt --input 3=-
+  kingdom_of_algorithmia bench 2024 4 --part 3 --iterations 100";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuestSelector {
+    Exact { event: Event, number: usize },
+    Latest,
+    LatestSolved,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run {
+        selector: QuestSelector,
+        config: RunConfig,
+        input: InputOptions,
+    },
+    Bench {
+        selector: QuestSelector,
+        part: Option<usize>,
+        input: InputOptions,
+        config: BenchConfig,
+        label: Option<String>,
+        record: bool,
+    },
+    BenchCompare {
+        baseline: Option<String>,
+        threshold: f64,
+    },
+    All {
+        config: RunConfig,
+        input: InputOptions,
+        tag: Option<String>,
+    },
+    Watch {
+        selector: QuestSelector,
+        config: RunConfig,
+        input: InputOptions,
+        interval: Duration,
+    },
+    Repl {
+        config: RunConfig,
+        input: InputOptions,
+    },
+    New {
+        event: Event,
+        number: usize,
+        title: String,
+    },
+    List {
+        tag: Option<String>,
+    },
+    Help,
+}
+
+pub type ArgsResult<T> = Result<T, String>;
+
+/// Options followed by a value and flags accepted by a command
+struct CommandSpec {
+    name: &'static str,
+    options: &'static [&'static str],
+    flags: &'static [&'static str],
+    /// Number of positional arguments after which the rest are words of a text, e.g. a title
+    words_after: Option<usize>,
+}
+
+const RUN: CommandSpec = CommandSpec {
+    name: "run",
+    options: &[
+        "--timeout",
+        "--jobs",
+        "--format",
+        "--input-dir",
+        "--input",
+        "--part",
+    ],
+    flags: &[
+        "--examples",
+        "--cache",
+        "--no-cache",
+        "--latest",
+        "--latest-solved",
+    ],
+    words_after: None,
+};
+
+const BENCH: CommandSpec = CommandSpec {
+    name: "bench",
+    options: &[
+        "--warmup",
+        "--iterations",
+        "--time-budget",
+        "--label",
+        "--input-dir",
+        "--input",
+        "--part",
+    ],
+    flags: &["--no-record", "--latest", "--latest-solved"],
+    words_after: None,
+};
+
+const BENCH_COMPARE: CommandSpec = CommandSpec {
+    name: "bench-compare",
+    options: &["--baseline", "--threshold"],
+    flags: &[],
+    words_after: None,
+};
+
+const ALL: CommandSpec = CommandSpec {
+    name: "all",
+    options: &["--timeout", "--jobs", "--format", "--input-dir", "--tag"],
+    flags: &["--examples", "--cache", "--no-cache"],
+    words_after: None,
+};
+
+const WATCH: CommandSpec = CommandSpec {
+    name: "watch",
+    options: &[
+        "--timeout",
+        "--interval",
+        "--input-dir",
+        "--input",
+        "--part",
+    ],
+    flags: &["--latest", "--latest-solved"],
+    words_after: None,
+};
+
+const REPL: CommandSpec = CommandSpec {
+    name: "repl",
+    options: &["--timeout", "--input-dir"],
+    flags: &[],
+    words_after: None,
+};
+
+// the title follows the event and quest number
+const NEW: CommandSpec = CommandSpec {
+    name: "new",
+    options: &[],
+    flags: &[],
+    words_after: Some(2),
+};
+
+const LIST: CommandSpec = CommandSpec {
+    name: "list",
+    options: &["--tag"],
+    flags: &[],
+    words_after: None,
+};
+
+const COMMANDS: &[&CommandSpec] = &[
+    &RUN,
+    &BENCH,
+    &BENCH_COMPARE,
+    &ALL,
+    &WATCH,
+    &REPL,
+    &NEW,
+    &LIST,
+];
+
+/// Whether each argument is in an option position, i.e. neither the value of an option nor
+/// a word of a text such as the title of `new`
+fn option_positions(args: &[&str]) -> Vec<bool> {
+    let mut positions = Vec::with_capacity(args.len());
+    let mut command = None;
+    let mut positional = 0;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        positions.push(true);
+        let Some(spec) = command else {
+            if !arg.starts_with('-') {
+                command = Some(COMMANDS.iter().find(|spec| spec.name == *arg).copied());
+            }
+            continue;
+        };
+        if arg.starts_with('-') {
+            if spec.is_some_and(|spec| spec.options.contains(arg)) && iter.next().is_some() {
+                positions.push(false);
+            }
+            continue;
+        }
+        positional += 1;
+        if spec.and_then(|spec| spec.words_after) == Some(positional) {
+            positions.extend(iter.by_ref().map(|_| false));
+        }
+    }
+    positions
+}
+
+/// Removes `-v` and `-q` flags placed in option positions, returns the verbosity (positive for
+/// `-v`, negative for `-q`) and the remaining arguments
+pub fn split_verbosity<T: AsRef<str>>(args: &[T]) -> ArgsResult<(i32, Vec<&str>)> {
+    let args = args.iter().map(|s| s.as_ref()).collect::<Vec<_>>();
+    let positions = option_positions(&args);
+    let mut verbose = 0;
+    let mut quiet = 0;
+    let mut rest = Vec::new();
+    for (arg, option) in args.into_iter().zip(positions) {
+        match arg {
+            "-v" | "--verbose" if option => verbose += 1,
+            "-vv" if option => verbose += 2,
+            "-q" | "--quiet" if option => quiet += 1,
+            "-qq" if option => quiet += 2,
+            _ => rest.push(arg),
+        }
+    }
+    if verbose > 0 && quiet > 0 {
+        return Err("--verbose and --quiet are mutually exclusive".to_string());
+    }
+    Ok((verbose - quiet, rest))
+}
+
+pub fn parse_args<T: AsRef<str>>(args: &[T]) -> ArgsResult<Command> {
+    let args = args.iter().map(|s| s.as_ref()).collect::<Vec<_>>();
+    let help = args
+        .iter()
+        .zip(option_positions(&args))
+        .any(|(arg, option)| option && matches!(*arg, "-h" | "--help"));
+    if help {
+        return Ok(Command::Help);
+    }
+    let Some((command, rest)) = args.split_first() else {
+        return Ok(Command::Help);
+    };
+    match *command {
+        "run" => parse_run(rest),
+        "bench" => parse_bench(rest),
+        "bench-compare" => parse_bench_compare(rest),
+        "all" => parse_all(rest),
+        "watch" => parse_watch(rest),
+        "repl" => parse_repl(rest),
+        "new" => parse_new(rest),
+        "list" => parse_list(rest),
+        "help" => Ok(Command::Help),
+        _ => Err(format!("unknown command '{}'", command)),
+    }
+}
+
+struct QuestArgs<'a> {
+    selector: QuestSelector,
+    part: Option<usize>,
+    options: Options<'a>,
+    flags: HashSet<&'a str>,
+}
+
+/// Option values in order of appearance, options may be repeated
+#[derive(Default)]
+struct Options<'a>(HashMap<&'a str, Vec<&'a str>>);
+
+impl<'a> Options<'a> {
+    fn insert(&mut self, key: &'a str, value: &'a str) {
+        self.0.entry(key).or_default().push(value);
+    }
+
+    /// The last value of the option
+    fn get(&self, key: &str) -> Option<&'a str> {
+        self.0.get(key).and_then(|values| values.last()).copied()
+    }
+
+    fn get_all(&self, key: &str) -> &[&'a str] {
+        self.0
+            .get(key)
+            .map(|values| values.as_slice())
+            .unwrap_or_default()
+    }
+
+    fn remove(&mut self, key: &str) -> Option<&'a str> {
+        self.0.remove(key).and_then(|values| values.last().copied())
+    }
+}
+
+struct OptionArgs<'a> {
+    positional: Vec<&'a str>,
+    options: Options<'a>,
+    flags: HashSet<&'a str>,
+}
+
+/// Splits arguments into positional ones, options followed by a value and flags.
+/// Options and flags must be listed in the command spec
+fn parse_options<'a>(args: &[&'a str], spec: &CommandSpec) -> ArgsResult<OptionArgs<'a>> {
+    let mut positional = Vec::new();
+    let mut options = Options::default();
+    let mut flags = HashSet::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if spec.options.contains(arg) {
+            let value = iter
+                .next()
+                .ok_or_else(|| format!("missing value for {}", arg))?;
+            options.insert(arg, value);
+        } else if spec.flags.contains(arg) {
+            flags.insert(*arg);
+        } else if arg.starts_with('-') {
+            return Err(format!("unknown option '{}'", arg));
+        } else {
+            positional.push(*arg);
+        }
+    }
+    Ok(OptionArgs {
+        positional,
+        options,
+        flags,
+    })
+}
+
+/// Parses quest selection and `--part` in addition to the other options and flags of the command
+fn parse_quest_args<'a>(args: &[&'a str], spec: &CommandSpec) -> ArgsResult<QuestArgs<'a>> {
+    let OptionArgs {
+        positional,
+        mut options,
+        mut flags,
+    } = parse_options(args, spec)?;
+    let part = options.remove("--part").map(parse_part).transpose()?;
+    let latest = match (flags.remove("--latest"), flags.remove("--latest-solved")) {
+        (true, true) => {
+            return Err("--latest and --latest-solved are mutually exclusive".to_string())
+        }
+        (true, false) => Some(QuestSelector::Latest),
+        (false, true) => Some(QuestSelector::LatestSolved),
+        (false, false) => None,
+    };
+    let selector = match (latest, positional.as_slice()) {
+        (Some(selector), []) => selector,
+        (Some(_), _) => return Err("--latest doesn't accept event or quest number".to_string()),
+        (None, [event, number]) => QuestSelector::Exact {
+            event: event.parse()?,
+            number: parse_number("quest", number)?,
+        },
+        (None, _) => return Err("expected <event> <quest> or --latest".to_string()),
+    };
+    Ok(QuestArgs {
+        selector,
+        part,
+        options,
+        flags,
+    })
+}
+
+fn parse_run(args: &[&str]) -> ArgsResult<Command> {
+    let QuestArgs {
+        selector,
+        part,
+        options,
+        flags,
+    } = parse_quest_args(args, &RUN)?;
+    let config = RunConfig {
+        part,
+        timeout: parse_timeout(&options)?,
+        examples: flags.contains("--examples"),
+        jobs: parse_jobs(&options)?,
+        format: parse_format(&options)?,
+        cache: parse_cache(&flags)?,
+    };
+    Ok(Command::Run {
+        selector,
+        config,
+        input: parse_input_options(&options)?,
+    })
+}
+
+fn parse_all(args: &[&str]) -> ArgsResult<Command> {
+    let OptionArgs {
+        positional,
+        options,
+        flags,
+    } = parse_options(args, &ALL)?;
+    expect_no_more(&positional)?;
+    let config = RunConfig {
+        part: None,
+        timeout: parse_timeout(&options)?,
+        examples: flags.contains("--examples"),
+        jobs: parse_jobs(&options)?,
+        format: parse_format(&options)?,
+        cache: parse_cache(&flags)?,
+    };
+    Ok(Command::All {
+        config,
+        input: parse_input_options(&options)?,
+        tag: options.get("--tag").map(|s| s.to_string()),
+    })
+}
+
+fn parse_watch(args: &[&str]) -> ArgsResult<Command> {
+    let QuestArgs {
+        selector,
+        part,
+        options,
+        ..
+    } = parse_quest_args(args, &WATCH)?;
+    let interval = match options.get("--interval") {
+        Some(value) => match parse_number("interval", value)? {
+            0 => return Err("interval must be positive".to_string()),
+            millis => Duration::from_millis(millis as u64),
+        },
+        None => watch::DEFAULT_INTERVAL,
+    };
+    let config = RunConfig {
+        part,
+        timeout: parse_timeout(&options)?,
+        ..Default::default()
+    };
+    Ok(Command::Watch {
+        selector,
+        config,
+        input: parse_input_options(&options)?,
+        interval,
+    })
+}
+
+fn parse_repl(args: &[&str]) -> ArgsResult<Command> {
+    let OptionArgs {
+        positional,
+        options,
+        ..
+    } = parse_options(args, &REPL)?;
+    expect_no_more(&positional)?;
+    let config = RunConfig {
+        timeout: parse_timeout(&options)?,
+        ..Default::default()
+    };
+    Ok(Command::Repl {
+        config,
+        input: parse_input_options(&options)?,
+    })
+}
+
+fn parse_list(args: &[&str]) -> ArgsResult<Command> {
+    let OptionArgs {
+        positional,
+        options,
+        ..
+    } = parse_options(args, &LIST)?;
+    expect_no_more(&positional)?;
+    Ok(Command::List {
+        tag: options.get("--tag").map(|s| s.to_string()),
+    })
+}
+
+fn parse_new(args: &[&str]) -> ArgsResult<Command> {
+    let [event, number, title @ ..] = args else {
+        return Err("expected <event> <quest> <title>".to_string());
+    };
+    // unquoted title words are joined, words starting with '-' are part of the title
+    let title = title.join(" ");
+    if title.trim().is_empty() {
+        return Err("missing quest title".to_string());
+    }
+    Ok(Command::New {
+        event: event.parse()?,
+        number: parse_number("quest", number)?,
+        title,
+    })
+}
+
+fn parse_input_options(options: &Options) -> ArgsResult<InputOptions> {
+    let mut parts = Vec::new();
+    for value in options.get_all("--input") {
+        let Some((part, path)) = value.split_once('=') else {
+            return Err(format!("expected --input N=PATH, got '{}'", value));
+        };
+        let source = match path {
+            "" => return Err(format!("missing path for input of part {}", part)),
+            "-" => InputSource::Stdin,
+            _ => InputSource::File(PathBuf::from(path)),
+        };
+        parts.push((parse_part(part)?, source));
+    }
+    Ok(InputOptions {
+        input_dir: options.get("--input-dir").map(PathBuf::from),
+        parts,
+    })
+}
+
+fn parse_timeout(options: &Options) -> ArgsResult<Option<Duration>> {
+    let Some(value) = options.get("--timeout") else {
+        return Ok(None);
+    };
+    let millis = parse_number("timeout", value)?;
+    if millis == 0 {
+        return Err("timeout must be positive".to_string());
+    }
+    Ok(Some(Duration::from_millis(millis as u64)))
+}
+
+/// Number of parallel jobs, `auto` uses all available cores
+fn parse_jobs(options: &Options) -> ArgsResult<usize> {
+    let jobs = match options.get("--jobs") {
+        Some("auto") => pool::available_jobs(),
+        Some(value) => parse_number("jobs", value)?,
+        None => 1,
+    };
+    if jobs == 0 {
+        return Err("jobs number must be positive".to_string());
+    }
+    Ok(jobs)
+}
+
+fn parse_format(options: &Options) -> ArgsResult<Format> {
+    options
+        .get("--format")
+        .map(|value| value.parse())
+        .transpose()
+        .map(|format| format.unwrap_or_default())
+}
+
+fn parse_cache(flags: &HashSet<&str>) -> ArgsResult<Option<bool>> {
+    match (flags.contains("--cache"), flags.contains("--no-cache")) {
+        (true, true) => Err("--cache and --no-cache are mutually exclusive".to_string()),
+        (true, false) => Ok(Some(true)),
+        (false, true) => Ok(Some(false)),
+        (false, false) => Ok(None),
+    }
+}
+
+fn parse_bench(args: &[&str]) -> ArgsResult<Command> {
+    let QuestArgs {
+        selector,
+        part,
+        options,
+        flags,
+    } = parse_quest_args(args, &BENCH)?;
+    let mut config = BenchConfig::default();
+    if let Some(value) = options.get("--warmup") {
+        config.warmup = parse_number("warmup", value)?;
+    }
+    match (options.get("--iterations"), options.get("--time-budget")) {
+        (Some(_), Some(_)) => {
+            return Err("--iterations and --time-budget are mutually exclusive".to_string())
+        }
+        (Some(value), None) => {
+            let count = parse_number("iterations", value)?;
+            if count == 0 {
+                return Err("iterations number must be positive".to_string());
+            }
+            config.limit = BenchLimit::Iterations(count);
+        }
+        (None, Some(value)) => {
+            let millis = parse_number("time budget", value)?;
+            config.limit = BenchLimit::TimeBudget(Duration::from_millis(millis as u64));
+        }
+        (None, None) => {}
+    }
+    Ok(Command::Bench {
+        selector,
+        part,
+        input: parse_input_options(&options)?,
+        config,
+        label: options.get("--label").map(|s| s.to_string()),
+        record: !flags.contains("--no-record"),
+    })
+}
+
+fn parse_bench_compare(args: &[&str]) -> ArgsResult<Command> {
+    let OptionArgs {
+        positional,
+        options,
+        ..
+    } = parse_options(args, &BENCH_COMPARE)?;
+    expect_no_more(&positional)?;
+    let threshold = match options.get("--threshold") {
+        Some(value) => value
+            .parse::<f64>()
+            .ok()
+            .filter(|x| x.is_finite() && *x >= 0.0)
+            .ok_or_else(|| format!("invalid threshold '{}'", value))?,
+        None => 10.0,
+    };
+    Ok(Command::BenchCompare {
+        baseline: options.get("--baseline").map(|s| s.to_string()),
+        threshold: threshold / 100.0,
+    })
+}
+
+fn parse_part(value: &str) -> ArgsResult<usize> {
+    let part = parse_number("part", value)?;
+    // declared parts are checked by the runner
+    if part == 0 {
+        return Err("part number must be positive".to_string());
+    }
+    Ok(part)
+}
+
+fn parse_number(name: &str, value: &str) -> ArgsResult<usize> {
+    value
+        .parse::<usize>()
+        .map_err(|_| format!("invalid {} number '{}'", name, value))
+}
+
+fn expect_no_more(args: &[&str]) -> ArgsResult<()> {
+    match args.first() {
+        Some(arg) => Err(format!("unexpected argument '{}'", arg)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cli_parse_run() {
+        let command = parse_args(&["run", "2024", "5", "--part", "2", "--timeout", "1500"]);
+        let expected = Command::Run {
+            selector: QuestSelector::Exact {
+                event: Event::Year(2024),
+                number: 5,
+            },
+            config: RunConfig {
+                part: Some(2),
+                timeout: Some(Duration::from_millis(1500)),
+                examples: false,
+                jobs: 1,
+                format: Format::Text,
+                cache: None,
+            },
+            input: InputOptions::default(),
+        };
+        assert_eq!(command.unwrap(), expected);
+        let args = [
+            "all",
+            "--timeout",
+            "10",
+            "--examples",
+            "--jobs",
+            "4",
+            "--format",
+            "json",
+            "--no-cache",
+        ];
+        let command = parse_args(&args).unwrap();
+        let expected = Command::All {
+            config: RunConfig {
+                part: None,
+                timeout: Some(Duration::from_millis(10)),
+                examples: true,
+                jobs: 4,
+                format: Format::Json,
+                cache: Some(false),
+            },
+            input: InputOptions::default(),
+            tag: None,
+        };
+        assert_eq!(command, expected);
+    }
+
+    #[test]
+    fn cli_parse_latest() {
+        let command = parse_args(&["run", "--latest"]).unwrap();
+        let expected = Command::Run {
+            selector: QuestSelector::Latest,
+            config: RunConfig::default(),
+            input: InputOptions::default(),
+        };
+        assert_eq!(command, expected);
+        assert!(parse_args(&["run", "--latest", "2024"]).is_err());
+        let command = parse_args(&["bench", "--latest-solved"]).unwrap();
+        assert!(matches!(
+            command,
+            Command::Bench {
+                selector: QuestSelector::LatestSolved,
+                ..
+            }
+        ));
+        assert!(parse_args(&["run", "--latest", "--latest-solved"]).is_err());
+        let command = parse_args(&["run", "s1", "3"]).unwrap();
+        assert!(matches!(
+            command,
+            Command::Run {
+                selector: QuestSelector::Exact {
+                    event: Event::Story(1),
+                    number: 3
+                },
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn cli_parse_new() {
+        let command = parse_args(&["new", "2024", "8", "Mystic", "Tower"]).unwrap();
+        let expected = Command::New {
+            event: Event::Year(2024),
+            number: 8,
+            title: "Mystic Tower".to_string(),
+        };
+        assert_eq!(command, expected);
+        assert!(parse_args(&["new", "2024", "8"]).is_err());
+    }
+
+    #[test]
+    fn cli_parse_watch() {
+        let args = ["watch", "2024", "7", "--part", "1", "--interval", "200"];
+        let expected = Command::Watch {
+            selector: QuestSelector::Exact {
+                event: Event::Year(2024),
+                number: 7,
+            },
+            config: RunConfig {
+                part: Some(1),
+                ..Default::default()
+            },
+            input: InputOptions::default(),
+            interval: Duration::from_millis(200),
+        };
+        assert_eq!(parse_args(&args).unwrap(), expected);
+        let Command::Watch { interval, .. } = parse_args(&["watch", "--latest"]).unwrap() else {
+            panic!("watch command expected");
+        };
+        assert_eq!(interval, watch::DEFAULT_INTERVAL);
+        assert!(parse_args(&["watch", "--latest", "--interval", "0"]).is_err());
+        assert!(parse_args(&["watch", "--latest", "--jobs", "2"]).is_err());
+    }
+
+    #[test]
+    fn cli_parse_repl() {
+        let command = parse_args(&["repl", "--timeout", "1000"]).unwrap();
+        let expected = Command::Repl {
+            config: RunConfig {
+                timeout: Some(Duration::from_millis(1000)),
+                ..Default::default()
+            },
+            input: InputOptions::default(),
+        };
+        assert_eq!(command, expected);
+        assert!(parse_args(&["repl", "2024"]).is_err());
+        assert!(parse_args(&["repl", "--input", "1=a.txt"]).is_err());
+    }
+
+    #[test]
+    fn cli_split_verbosity() {
+        let (verbosity, rest) = split_verbosity(&["-v", "run", "2024", "7", "-v"]).unwrap();
+        assert_eq!(verbosity, 2);
+        assert_eq!(rest, ["run", "2024", "7"]);
+        assert_eq!(split_verbosity(&["all", "-qq"]).unwrap().0, -2);
+        assert_eq!(split_verbosity(&["list"]).unwrap(), (0, vec!["list"]));
+        assert!(split_verbosity(&["-v", "all", "-q"]).is_err());
+
+        // option values and title words are not flags
+        let (verbosity, rest) = split_verbosity(&["-v", "new", "2024", "8", "-q"]).unwrap();
+        assert_eq!(verbosity, 1);
+        let expected = Command::New {
+            event: Event::Year(2024),
+            number: 8,
+            title: "-q".to_string(),
+        };
+        assert_eq!(parse_args(&rest).unwrap(), expected);
+        let (verbosity, rest) = split_verbosity(&["bench", "2024", "4", "--label", "-v"]).unwrap();
+        assert_eq!(verbosity, 0);
+        let Command::Bench { label, .. } = parse_args(&rest).unwrap() else {
+            panic!("bench command expected");
+        };
+        assert_eq!(label.as_deref(), Some("-v"));
+    }
+
+    #[test]
+    fn cli_parse_list() {
+        let command = parse_args(&["list", "--tag", "grid"]).unwrap();
+        let expected = Command::List {
+            tag: Some("grid".to_string()),
+        };
+        assert_eq!(command, expected);
+        assert_eq!(parse_args(&["list"]).unwrap(), Command::List { tag: None });
+        assert!(parse_args(&["list", "grid"]).is_err());
+    }
+
+    #[test]
+    fn cli_parse_input() {
+        let args = ["run", "--latest", "--input", "1=a.txt", "--input", "3=-"];
+        let Command::Run { input, .. } = parse_args(&args).unwrap() else {
+            panic!("run command expected");
+        };
+        let expected = InputOptions {
+            input_dir: None,
+            parts: vec![
+                (1, InputSource::File(PathBuf::from("a.txt"))),
+                (3, InputSource::Stdin),
+            ],
+        };
+        assert_eq!(input, expected);
+        let args = ["all", "--input-dir", "../inputs"];
+        let Command::All { input, .. } = parse_args(&args).unwrap() else {
+            panic!("all command expected");
+        };
+        assert_eq!(input.input_dir, Some(PathBuf::from("../inputs")));
+        assert!(parse_args(&["run", "--latest", "--input", "a.txt"]).is_err());
+        assert!(parse_args(&["run", "--latest", "--input", "0=a.txt"]).is_err());
+        assert!(parse_args(&["all", "--input", "1=a.txt"]).is_err());
+    }
+
+    #[test]
+    fn cli_parse_bench() {
+        let command = parse_args(&["bench", "2024", "4", "--iterations", "50", "--warmup", "0"]);
+        let expected = Command::Bench {
+            selector: QuestSelector::Exact {
+                event: Event::Year(2024),
+                number: 4,
+            },
+            part: None,
+            input: InputOptions::default(),
+            config: BenchConfig {
+                warmup: 0,
+                limit: BenchLimit::Iterations(50),
+            },
+            label: None,
+            record: true,
+        };
+        assert_eq!(command.unwrap(), expected);
+        let args = [
+            "bench",
+            "--latest",
+            "--iterations",
+            "5",
+            "--time-budget",
+            "5",
+        ];
+        assert!(parse_args(&args).is_err());
+        assert!(parse_args(&["run", "--latest", "--iterations", "5"]).is_err());
+    }
+
+    #[test]
+    fn cli_parse_bench_compare() {
+        let command = parse_args(&["bench-compare", "--threshold", "25", "--baseline", "v1"]);
+        let expected = Command::BenchCompare {
+            baseline: Some("v1".to_string()),
+            threshold: 0.25,
+        };
+        assert_eq!(command.unwrap(), expected);
+        assert!(parse_args(&["bench-compare", "--threshold", "-1"]).is_err());
+    }
+
+    #[test]
+    fn cli_parse_errors() {
+        assert!(parse_args(&["run", "2024"]).is_err());
+        assert!(parse_args(&["run", "2024", "x"]).is_err());
+        assert!(parse_args(&["run", "2024", "1", "--part", "0"]).is_err());
+        assert!(parse_args(&["run", "2024", "1", "--part"]).is_err());
+        assert!(parse_args(&["run", "2024", "1", "--timeout", "0"]).is_err());
+        assert!(parse_args(&["all", "--jobs", "0"]).is_err());
+        assert!(parse_args(&["all", "--jobs", "many"]).is_err());
+        assert!(parse_args(&["run", "2024", "1", "--format", "xml"]).is_err());
+        assert!(parse_args(&["all", "--cache", "--no-cache"]).is_err());
+        assert!(parse_args(&["unknown"]).is_err());
+    }
+
+    #[test]
+    fn cli_parse_help() {
+        let empty: [&str; 0] = [];
+        assert_eq!(parse_args(&empty).unwrap(), Command::Help);
+        assert_eq!(parse_args(&["run", "--help"]).unwrap(), Command::Help);
+        assert_eq!(parse_args(&["-h", "new"]).unwrap(), Command::Help);
+        // option values and title words are not flags
+        let expected = Command::New {
+            event: Event::Year(2024),
+            number: 8,
+            title: "-h".to_string(),
+        };
+        assert_eq!(parse_args(&["new", "2024", "8", "-h"]).unwrap(), expected);
+        let command = parse_args(&["bench", "2024", "4", "--label", "--help"]).unwrap();
+        let Command::Bench { label, .. } = command else {
+            panic!("bench command expected");
+        };
+        assert_eq!(label.as_deref(), Some("--help"));
+        let expected = Command::All {
+            config: RunConfig::default(),
+            input: InputOptions::default(),
+            tag: None,
+        };
+        assert_eq!(parse_args(&["all"]).unwrap(), expected);
+    }
+}
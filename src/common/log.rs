@@ -0,0 +1,210 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// Environment variable with the per-module filter, e.g. `quests::quest_2024_07=trace,warn`
+pub const LOG_ENV: &str = "KOA_LOG";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    /// Default level raised by `-v` and lowered by `-q` flags
+    pub fn with_verbosity(verbosity: i32) -> Self {
+        match verbosity {
+            ..=-2 => Level::Error,
+            -1 => Level::Warn,
+            0 => Level::Info,
+            1 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "error" => Ok(Level::Error),
+            "warn" => Ok(Level::Warn),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(format!(
+                "unknown log level '{}', expected error, warn, info, debug or trace",
+                s
+            )),
+        }
+    }
+}
+
+/// Most verbose level enabled by default and for modules, modules are paths within the crate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    default: Level,
+    modules: Vec<(String, Level)>,
+}
+
+impl Filter {
+    pub fn new(default: Level) -> Self {
+        Self {
+            default,
+            modules: Vec::new(),
+        }
+    }
+
+    /// Applies comma-separated `level` or `module=level` directives
+    pub fn with_directives(mut self, directives: &str) -> Result<Self, String> {
+        for directive in directives.split(',').map(str::trim) {
+            match directive.split_once('=') {
+                _ if directive.is_empty() => {}
+                Some((module, level)) => {
+                    let module = module.trim().trim_start_matches("crate::");
+                    self.modules
+                        .push((module.to_string(), level.trim().parse()?));
+                }
+                None => self.default = directive.parse()?,
+            }
+        }
+        Ok(self)
+    }
+
+    /// Applies directives of the `KOA_LOG` environment variable
+    pub fn with_env(self) -> Result<Self, String> {
+        match std::env::var(LOG_ENV) {
+            Ok(directives) => self
+                .with_directives(&directives)
+                .map_err(|err| format!("{}: {}", LOG_ENV, err)),
+            Err(_) => Ok(self),
+        }
+    }
+
+    /// The most specific module directive wins, `target` is a `module_path!()`
+    pub fn enabled(&self, level: Level, target: &str) -> bool {
+        let path = local_path(target);
+        let max = self
+            .modules
+            .iter()
+            .filter(|(module, _)| {
+                path.strip_prefix(module.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+            .max_by_key(|(module, _)| module.len())
+            .map_or(self.default, |(_, level)| *level);
+        level <= max
+    }
+}
+
+static FILTER: OnceLock<Filter> = OnceLock::new();
+
+/// Sets the filter of the process, messages are filtered at the info level until then
+pub fn init(filter: Filter) {
+    FILTER.set(filter).ok();
+}
+
+pub fn enabled(level: Level, target: &str) -> bool {
+    FILTER
+        .get_or_init(|| Filter::new(Level::Info))
+        .enabled(level, target)
+}
+
+/// Messages of a running part are kept with its result, others go to stderr
+pub fn write(level: Level, target: &str, args: fmt::Arguments) {
+    let message = args.to_string();
+    if !crate::quests::context::record(level, &message) {
+        eprintln!("[{}] {}: {}", level, local_path(target), message);
+    }
+}
+
+/// Module path without the crate name
+fn local_path(target: &str) -> &str {
+    target.split_once("::").map_or("", |(_, path)| path)
+}
+
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::common::log::enabled($level, module_path!()) {
+            $crate::common::log::write($level, module_path!(), format_args!($($arg)+));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::log!($crate::common::log::Level::Error, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::log!($crate::common::log::Level::Warn, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log!($crate::common::log::Level::Info, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log!($crate::common::log::Level::Debug, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log!($crate::common::log::Level::Trace, $($arg)+) };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn log_filter() {
+        let filter = Filter::new(Level::Info)
+            .with_directives("quests=debug, quests::quest_2024_07=warn")
+            .unwrap();
+        let target = "kingdom_of_algorithmia::quests::quest_2024_05";
+        assert!(filter.enabled(Level::Debug, target));
+        assert!(!filter.enabled(Level::Trace, target));
+        assert!(!filter.enabled(Level::Info, "kingdom_of_algorithmia::quests::quest_2024_07"));
+        // module names match whole path segments
+        assert!(!filter.enabled(Level::Debug, "kingdom_of_algorithmia::quests_extra"));
+        assert!(filter.enabled(Level::Info, "kingdom_of_algorithmia::runner"));
+
+        let filter = Filter::new(Level::Info).with_directives("error").unwrap();
+        assert!(!filter.enabled(Level::Warn, "kingdom_of_algorithmia::runner"));
+        assert!(Filter::new(Level::Info)
+            .with_directives("runner=loud")
+            .is_err());
+    }
+
+    #[test]
+    fn log_verbosity() {
+        assert_eq!(Level::with_verbosity(0), Level::Info);
+        assert_eq!(Level::with_verbosity(-1), Level::Warn);
+        assert_eq!(Level::with_verbosity(-5), Level::Error);
+        assert_eq!(Level::with_verbosity(2), Level::Trace);
+        assert_eq!("DEBUG".parse(), Ok(Level::Debug));
+    }
+}
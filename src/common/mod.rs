@@ -1,3 +1,4 @@
+pub mod log;
 pub mod strings;
 
 pub type Vec2<T> = Vec<Vec<T>>;
@@ -12,10 +12,51 @@ pub fn reversed_str(s: &str) -> String {
 
 pub trait TrimmedSplit {
     fn trimmed_split(&self) -> impl Iterator<Item = &str>;
+
+    /// Same as `trimmed_split` but keeps 1-based line numbers for error reporting
+    fn numbered_trimmed_split(&self) -> impl Iterator<Item = (usize, &str)>;
 }
 
 impl TrimmedSplit for str {
     fn trimmed_split(&self) -> impl Iterator<Item = &str> {
         self.split('\n').map(|s| s.trim()).filter(|s| !s.is_empty())
     }
+
+    fn numbered_trimmed_split(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.split('\n')
+            .enumerate()
+            .map(|(i, s)| (i + 1, s.trim()))
+            .filter(|(_, s)| !s.is_empty())
+    }
+}
+
+/// Strips the byte order mark, converts CRLF line endings to LF and removes trailing blank
+/// lines, the terminator of the last non-blank line is kept
+pub fn normalized(input: &str) -> String {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let mut output = input.replace("\r\n", "\n");
+    let len = output.trim_end().len();
+    // keep trailing spaces and the line break of the last non-blank line
+    let len = match output[len..].find('\n') {
+        _ if len == 0 => 0,
+        Some(pos) => len + pos + 1,
+        None => output.len(),
+    };
+    output.truncate(len);
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn strings_normalized() {
+        let input = "\u{feff}ab\r\n c \r\n\r\n  \n";
+        assert_eq!(normalized(input), "ab\n c \n");
+        assert_eq!(normalized("a\n"), "a\n");
+        assert_eq!(normalized("a"), "a");
+        assert_eq!(normalized(" \n\n"), "");
+        assert_eq!(normalized(""), "");
+    }
 }
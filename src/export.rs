@@ -0,0 +1,242 @@
+use crate::quests::context::Diagnostics;
+use crate::quests::Event;
+use crate::runner::{Outcome, PartReport, QuestReport};
+use std::str::FromStr;
+
+/// Output format of `run` and `all`, machine-readable formats print one record per part
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!(
+                "unknown format '{}', expected text, json or csv",
+                s
+            )),
+        }
+    }
+}
+
+const CSV_HEADER: &str = "event,quest,part,answer,status,cached,duration_ns,error,counters";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub event: Event,
+    pub quest: usize,
+    pub part: usize,
+    pub answer: Option<String>,
+    pub status: &'static str,
+    pub cached: bool,
+    /// Parse and solve time, cached answers keep the time of the run that produced them
+    pub duration_ns: u128,
+    pub error: Option<String>,
+    /// Messages and counters reported by the solution, only counters are written to CSV
+    pub diagnostics: Diagnostics,
+}
+
+impl Record {
+    fn new(quest: &QuestReport, part: &PartReport) -> Self {
+        let (answer, error) = match &part.outcome {
+            Outcome::Solved(answer) => (Some(answer.to_string()), None),
+            Outcome::Failed(err) => (None, Some(err.to_string())),
+            Outcome::Panicked(report) => (None, Some(report.to_string())),
+            Outcome::Timeout => (None, Some("timeout".to_string())),
+            Outcome::NoInput(err) => (None, Some(err.to_string())),
+        };
+        Self {
+            event: quest.event,
+            quest: quest.quest,
+            part: part.part,
+            answer,
+            status: part.status(),
+            cached: part.cached,
+            duration_ns: part.total().as_nanos(),
+            error,
+            diagnostics: part.diagnostics.clone(),
+        }
+    }
+
+    fn to_json(&self) -> String {
+        let optional =
+            |value: &Option<String>| value.as_deref().map_or("null".to_string(), json_string);
+        let counters = self
+            .diagnostics
+            .counters
+            .iter()
+            .map(|(name, value)| format!("{}: {}", json_string(name), value))
+            .collect::<Vec<_>>();
+        let log = self
+            .diagnostics
+            .logs
+            .iter()
+            .map(|entry| {
+                format!(
+                    "{{\"level\": {}, \"elapsed_ns\": {}, \"message\": {}}}",
+                    json_string(&entry.level.to_string()),
+                    entry.elapsed.as_nanos(),
+                    json_string(&entry.message)
+                )
+            })
+            .collect::<Vec<_>>();
+        format!(
+            "{{\"event\": {}, \"quest\": {}, \"part\": {}, \"answer\": {}, \"status\": {}, \"cached\": {}, \"duration_ns\": {}, \"error\": {}, \"counters\": {{{}}}, \"log\": [{}]}}",
+            json_string(&self.event.to_string()),
+            self.quest,
+            self.part,
+            optional(&self.answer),
+            json_string(self.status),
+            self.cached,
+            self.duration_ns,
+            optional(&self.error),
+            counters.join(", "),
+            log.join(", ")
+        )
+    }
+
+    fn to_csv(&self) -> String {
+        let counters = self
+            .diagnostics
+            .counters
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>();
+        [
+            self.event.to_string(),
+            self.quest.to_string(),
+            self.part.to_string(),
+            csv_field(self.answer.as_deref().unwrap_or_default()),
+            self.status.to_string(),
+            self.cached.to_string(),
+            self.duration_ns.to_string(),
+            csv_field(self.error.as_deref().unwrap_or_default()),
+            csv_field(&counters.join(";")),
+        ]
+        .join(",")
+    }
+}
+
+/// Records of the solved parts, examples are not included
+pub fn records(reports: &[QuestReport]) -> Vec<Record> {
+    reports
+        .iter()
+        .flat_map(|quest| quest.parts.iter().map(|part| Record::new(quest, part)))
+        .collect()
+}
+
+pub fn render(format: Format, records: &[Record]) -> String {
+    match format {
+        Format::Text => String::new(),
+        Format::Json => {
+            let items = records
+                .iter()
+                .map(|r| format!("  {}", r.to_json()))
+                .collect::<Vec<_>>();
+            if items.is_empty() {
+                "[]".to_string()
+            } else {
+                format!("[\n{}\n]", items.join(",\n"))
+            }
+        }
+        Format::Csv => std::iter::once(CSV_HEADER.to_string())
+            .chain(records.iter().map(|r| r.to_csv()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            ch if ch.is_control() => result.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => result.push(ch),
+        }
+    }
+    result.push('"');
+    result
+}
+
+/// Quotes the field if it contains separators, quotes or line breaks
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::common::log::Level;
+    use crate::quests::context::LogEntry;
+    use std::time::Duration;
+
+    fn record(answer: Option<&str>, error: Option<&str>) -> Record {
+        Record {
+            event: Event::Story(1),
+            quest: 2,
+            part: 3,
+            answer: answer.map(|s| s.to_string()),
+            status: if error.is_some() { "ERROR" } else { "PASS" },
+            cached: false,
+            duration_ns: 1500,
+            error: error.map(|s| s.to_string()),
+            diagnostics: Diagnostics::default(),
+        }
+    }
+
+    #[test]
+    fn export_json() {
+        let mut solved = record(Some("42"), None);
+        solved.diagnostics.counters.insert("rounds", 7);
+        solved.diagnostics.logs.push(LogEntry {
+            level: Level::Info,
+            elapsed: Duration::from_nanos(200),
+            message: "cycle found".to_string(),
+        });
+        let records = [solved, record(None, Some("bad \"input\"\n"))];
+        let expected = r#"[
+  {"event": "s1", "quest": 2, "part": 3, "answer": "42", "status": "PASS", "cached": false, "duration_ns": 1500, "error": null, "counters": {"rounds": 7}, "log": [{"level": "info", "elapsed_ns": 200, "message": "cycle found"}]},
+  {"event": "s1", "quest": 2, "part": 3, "answer": null, "status": "ERROR", "cached": false, "duration_ns": 1500, "error": "bad \"input\"\n", "counters": {}, "log": []}
+]"#;
+        assert_eq!(render(Format::Json, &records), expected);
+        assert_eq!(render(Format::Json, &[]), "[]");
+    }
+
+    #[test]
+    fn export_csv() {
+        let mut solved = record(Some("42"), None);
+        solved.diagnostics.counters.insert("rounds", 7);
+        solved.diagnostics.counters.insert("states", 3);
+        let records = [solved, record(None, Some("a, \"b\""))];
+        let expected = "event,quest,part,answer,status,cached,duration_ns,error,counters
+s1,2,3,42,PASS,false,1500,,rounds=7;states=3
+s1,2,3,,ERROR,false,1500,\"a, \"\"b\"\"\",";
+        assert_eq!(render(Format::Csv, &records), expected);
+    }
+
+    #[test]
+    fn export_format() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert_eq!("csv".parse(), Ok(Format::Csv));
+        assert!("xml".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,296 @@
+use crate::bench::{format_precise, Stats};
+use crate::quests::Event;
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const HISTORY_FILE: &str = "bench_history.tsv";
+
+const HEADER: &str =
+    "timestamp\tdate\tlabel\tevent\tquest\tpart\tsamples\tmin_ns\tmedian_ns\tmean_ns\tp95_ns\tstd_dev_ns";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub timestamp: u64,
+    pub label: String,
+    pub event: Event,
+    pub quest: usize,
+    pub part: usize,
+    pub stats: Stats,
+}
+
+impl Record {
+    fn key(&self) -> (Event, usize, usize) {
+        (self.event, self.quest, self.part)
+    }
+
+    fn to_line(&self) -> String {
+        let stats = &self.stats;
+        [
+            self.timestamp.to_string(),
+            format_date(self.timestamp),
+            self.label.clone(),
+            self.event.to_string(),
+            self.quest.to_string(),
+            self.part.to_string(),
+            stats.samples.to_string(),
+            stats.min.as_nanos().to_string(),
+            stats.median.as_nanos().to_string(),
+            stats.mean.as_nanos().to_string(),
+            stats.p95.as_nanos().to_string(),
+            stats.std_dev.as_nanos().to_string(),
+        ]
+        .join("\t")
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let fields = line.split('\t').collect::<Vec<_>>();
+        let [timestamp, _, label, event, quest, part, samples, min, median, mean, p95, std_dev] =
+            fields.as_slice()
+        else {
+            return None;
+        };
+        let nanos = |s: &str| s.parse::<u64>().ok().map(Duration::from_nanos);
+        Some(Self {
+            timestamp: timestamp.parse().ok()?,
+            label: label.to_string(),
+            event: event.parse().ok()?,
+            quest: quest.parse().ok()?,
+            part: part.parse().ok()?,
+            stats: Stats {
+                samples: samples.parse().ok()?,
+                min: nanos(min)?,
+                median: nanos(median)?,
+                mean: nanos(mean)?,
+                p95: nanos(p95)?,
+                std_dev: nanos(std_dev)?,
+            },
+        })
+    }
+}
+
+/// The history file is kept in the crate root, so runs from any directory of the checkout share it
+pub fn history_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(HISTORY_FILE)
+}
+
+pub fn load<P: AsRef<Path>>(path: P) -> std::io::Result<Vec<Record>> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let records = content
+        .lines()
+        .filter(|line| !line.is_empty() && *line != HEADER)
+        .filter_map(Record::from_line)
+        .collect();
+    Ok(records)
+}
+
+pub fn append<P: AsRef<Path>>(path: P, records: &[Record]) -> std::io::Result<()> {
+    let path = path.as_ref();
+    let is_new = !path.exists();
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if is_new {
+        writeln!(file, "{}", HEADER)?;
+    }
+    for record in records {
+        writeln!(file, "{}", record.to_line())?;
+    }
+    Ok(())
+}
+
+/// Short hash of the current git revision of the crate with '-dirty' suffix for uncommitted changes
+pub fn git_revision() -> Option<String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    let revision = git(&["rev-parse", "--short", "HEAD"])?;
+    let is_dirty = git(&["status", "--porcelain", "--untracked-files=no"])
+        .is_some_and(|status| !status.is_empty());
+    if is_dirty {
+        Some(format!("{}-dirty", revision))
+    } else {
+        Some(revision)
+    }
+}
+
+pub fn now_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Formats unix timestamp as UTC date (yyyy-mm-dd)
+pub fn format_date(timestamp: u64) -> String {
+    // days to civil date conversion, see http://howardhinnant.github.io/date_algorithms.html
+    let days = (timestamp / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub latest: Record,
+    pub baseline: Option<Record>,
+}
+
+impl Comparison {
+    /// Relative change of the median time, positive values mean slowdown
+    pub fn change(&self) -> Option<f64> {
+        let baseline = self.baseline.as_ref()?.stats.median.as_nanos() as f64;
+        if baseline == 0.0 {
+            return None;
+        }
+        let latest = self.latest.stats.median.as_nanos() as f64;
+        Some((latest - baseline) / baseline)
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change().is_some_and(|change| change > threshold)
+    }
+}
+
+/// Compares the latest record of every quest part with a baseline record.
+/// If baseline label isn't specified, the most recent record with another label is used
+pub fn compare(records: &[Record], baseline: Option<&str>) -> Vec<Comparison> {
+    let mut latest = HashMap::<(Event, usize, usize), &Record>::new();
+    for record in records {
+        let entry = latest.entry(record.key()).or_insert(record);
+        if record.timestamp >= entry.timestamp {
+            *entry = record;
+        }
+    }
+    let mut result = latest
+        .into_values()
+        .map(|latest| {
+            let baseline = records
+                .iter()
+                .filter(|r| r.key() == latest.key() && r.timestamp <= latest.timestamp)
+                .filter(|r| match baseline {
+                    Some(label) => r.label == label && !std::ptr::eq(*r, latest),
+                    None => r.label != latest.label,
+                })
+                .max_by_key(|r| r.timestamp)
+                .cloned();
+            Comparison {
+                latest: latest.clone(),
+                baseline,
+            }
+        })
+        .collect::<Vec<_>>();
+    result.sort_by_key(|c| c.latest.key());
+    result
+}
+
+pub fn print_comparison(comparisons: &[Comparison], threshold: f64) {
+    for item in comparisons {
+        let latest = &item.latest;
+        let title = format!(
+            "{} {:>2} part {} ({})",
+            latest.event, latest.quest, latest.part, latest.label
+        );
+        let Some(baseline) = &item.baseline else {
+            println!(
+                "{:>32}: {} [NO BASELINE]",
+                title,
+                format_precise(latest.stats.median)
+            );
+            continue;
+        };
+        let change = item.change().unwrap_or_default() * 100.0;
+        let status = if item.is_regression(threshold) {
+            "SLOWER"
+        } else {
+            "OK"
+        };
+        println!(
+            "{:>32}: {} vs {} ({}) {:+.1}% [{}]",
+            title,
+            format_precise(latest.stats.median),
+            format_precise(baseline.stats.median),
+            baseline.label,
+            change,
+            status
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn record(timestamp: u64, label: &str, part: usize, median_us: u64) -> Record {
+        let median = Duration::from_micros(median_us);
+        Record {
+            timestamp,
+            label: label.to_string(),
+            event: Event::Year(2024),
+            quest: 4,
+            part,
+            stats: Stats {
+                samples: 10,
+                min: median,
+                median,
+                mean: median,
+                p95: median,
+                std_dev: Duration::ZERO,
+            },
+        }
+    }
+
+    #[test]
+    fn history_record_line() {
+        let record = record(1_730_000_000, "abc123", 3, 250);
+        let line = record.to_line();
+        assert!(line.starts_with("1730000000\t2024-10-27\tabc123\t2024\t4\t3\t10\t250000"));
+        assert_eq!(Record::from_line(&line), Some(record));
+        assert_eq!(Record::from_line(HEADER), None);
+    }
+
+    #[test]
+    fn history_format_date() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(951_782_400), "2000-02-29");
+        assert_eq!(format_date(1_735_689_599), "2024-12-31");
+    }
+
+    #[test]
+    fn history_compare() {
+        let records = [
+            record(1, "old", 1, 100),
+            record(2, "base", 1, 100),
+            record(2, "base", 2, 100),
+            record(3, "new", 1, 150),
+            record(3, "new", 2, 105),
+        ];
+        let result = compare(&records, None);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].baseline.as_ref().unwrap().label, "base");
+        assert!(result[0].is_regression(0.1));
+        assert!(!result[1].is_regression(0.1));
+
+        let result = compare(&records, Some("old"));
+        assert_eq!(result[0].change(), Some(0.5));
+        assert!(result[1].baseline.is_none());
+    }
+}
@@ -0,0 +1,94 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::fmt::Display;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicReport {
+    pub message: String,
+    pub location: Option<String>,
+}
+
+impl Display for PanicReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "panicked: {}", self.message)?;
+        if let Some(location) = &self.location {
+            write!(f, " at {}", location)?;
+        }
+        Ok(())
+    }
+}
+
+thread_local! {
+    static IS_ISOLATED: Cell<bool> = const { Cell::new(false) };
+    static LAST_PANIC: RefCell<Option<PanicReport>> = const { RefCell::new(None) };
+}
+
+static HOOK: Once = Once::new();
+
+/// Replaces the panic hook to capture panics inside of `catch_panic` silently,
+/// panics outside of isolated calls are passed to the default hook
+fn install_hook() {
+    HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !IS_ISOLATED.with(|flag| flag.get()) {
+                default_hook(info);
+                return;
+            }
+            let report = PanicReport {
+                message: payload_message(info.payload()),
+                location: info
+                    .location()
+                    .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+            };
+            LAST_PANIC.with(|last| *last.borrow_mut() = Some(report));
+        }));
+    });
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// Runs the closure and converts a panic into the report with message and location
+pub fn catch_panic<T, F: FnOnce() -> T>(f: F) -> Result<T, PanicReport> {
+    install_hook();
+    let was_isolated = IS_ISOLATED.with(|flag| flag.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    IS_ISOLATED.with(|flag| flag.set(was_isolated));
+    result.map_err(|payload| {
+        LAST_PANIC
+            .with(|last| last.borrow_mut().take())
+            .unwrap_or_else(|| PanicReport {
+                message: payload_message(payload.as_ref()),
+                location: None,
+            })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn isolation_catch_panic() {
+        assert_eq!(catch_panic(|| 42), Ok(42));
+        let report = catch_panic(|| {
+            let values: Vec<i32> = Vec::new();
+            values[1]
+        })
+        .unwrap_err();
+        assert!(report.message.contains("index out of bounds"));
+        assert!(report.location.unwrap().starts_with("src/isolation.rs:"));
+        let report = catch_panic(|| panic!("Invalid format {}", 1)).unwrap_err();
+        assert_eq!(report.message, "Invalid format 1");
+    }
+}
@@ -1,41 +1,272 @@
-use crate::quests::{Quest, QuestFactory};
-use std::time::Instant;
+use crate::cli::{Command, QuestSelector};
+use crate::common::log::{self, Filter, Level};
+use crate::export::Format;
+use crate::quests::{event, Quest, QuestInfo, QuestRegistry};
+use std::process::ExitCode;
 
+mod bench;
+mod cache;
+mod cli;
 mod common;
+mod export;
+mod history;
+mod isolation;
+mod pool;
 mod quests;
+mod repl;
+mod runner;
+mod scaffold;
+mod watch;
+mod worker;
 
-fn main() {
-    println!("Kingdom of Algorithmia");
-    let factory = QuestFactory;
-    let Some(quest) = factory.custom() else {
-        println!("[Warn] No Quest");
-        return;
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let (verbosity, args) = match cli::split_verbosity(&args) {
+        Ok(split) => split,
+        Err(err) => {
+            eprintln!("[Error] {}", err);
+            return ExitCode::FAILURE;
+        }
     };
-    execute(&quest);
+    match Filter::new(Level::with_verbosity(verbosity)).with_env() {
+        Ok(filter) => log::init(filter),
+        Err(err) => {
+            eprintln!("[Error] {}", err);
+            return ExitCode::FAILURE;
+        }
+    }
+    let command = match cli::parse_args(&args) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("[Error] {}", err);
+            eprintln!("Run with --help to see usage");
+            return ExitCode::FAILURE;
+        }
+    };
+    let registry = QuestRegistry::new();
+    match command {
+        Command::Help => println!("{}", cli::USAGE),
+        Command::List { tag } => list(&registry, tag.as_deref()),
+        Command::New {
+            event,
+            number,
+            title,
+        } => {
+            let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+            match scaffold::create(root, event, number, &title) {
+                Ok(created) => {
+                    for path in created {
+                        println!("Created {}", path.display());
+                    }
+                    println!("Registered quest {} {}", event, number);
+                }
+                Err(err) => {
+                    eprintln!("[Error] {}", err);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Command::Run {
+            selector,
+            config,
+            input,
+        } => {
+            if config.format == Format::Text {
+                println!("Kingdom of Algorithmia");
+            }
+            let Some(mut quest) = select(&registry, selector) else {
+                return ExitCode::FAILURE;
+            };
+            quest.input_loader.configure(&input.with_env());
+            let reports = runner::execute_all(&[(label(&quest.info), quest)], &config);
+            print_records(config.format, &reports);
+            if reports.iter().any(|r| r.has_failures()) {
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::Bench {
+            selector,
+            part,
+            input,
+            config,
+            label,
+            record,
+        } => {
+            println!("Kingdom of Algorithmia");
+            let Some(mut quest) = select(&registry, selector) else {
+                return ExitCode::FAILURE;
+            };
+            quest.input_loader.configure(&input.with_env());
+            let results = bench::bench(&quest, part, &config);
+            if record && !results.is_empty() {
+                let label = label
+                    .or_else(history::git_revision)
+                    .unwrap_or("unknown".to_string());
+                let timestamp = history::now_timestamp();
+                let records = results
+                    .into_iter()
+                    .map(|(part, stats)| history::Record {
+                        timestamp,
+                        label: label.clone(),
+                        event: quest.info.event,
+                        quest: quest.info.number,
+                        part,
+                        stats,
+                    })
+                    .collect::<Vec<_>>();
+                let path = history::history_path();
+                if let Err(err) = history::append(&path, &records) {
+                    eprintln!("[Error] failed to write benchmark history: {}", err);
+                    return ExitCode::FAILURE;
+                }
+                println!("Results are saved to {} as '{}'", path.display(), label);
+            }
+        }
+        Command::Watch {
+            selector,
+            config,
+            input,
+            interval,
+        } => {
+            println!("Kingdom of Algorithmia");
+            let Some(mut quest) = select(&registry, selector) else {
+                return ExitCode::FAILURE;
+            };
+            quest.input_loader.configure(&input.with_env());
+            if let Err(err) = watch::watch(label(&quest.info), &quest, &config, interval) {
+                eprintln!("[Error] {}", err);
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::Repl { config, input } => {
+            println!("Kingdom of Algorithmia");
+            repl::repl(&registry, &config, &input.with_env());
+        }
+        Command::BenchCompare {
+            baseline,
+            threshold,
+        } => {
+            let records = match history::load(history::history_path()) {
+                Ok(records) => records,
+                Err(err) => {
+                    eprintln!("[Error] failed to read benchmark history: {}", err);
+                    return ExitCode::FAILURE;
+                }
+            };
+            let comparisons = history::compare(&records, baseline.as_deref());
+            if comparisons.is_empty() {
+                println!("Benchmark history is empty");
+            }
+            history::print_comparison(&comparisons, threshold);
+            if comparisons.iter().any(|c| c.is_regression(threshold)) {
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::All { config, input, tag } => {
+            if config.format == Format::Text {
+                println!("Kingdom of Algorithmia");
+            }
+            let input = input.with_env();
+            let quests = registry
+                .all()
+                .filter(|quest| tag.as_deref().is_none_or(|tag| quest.info.has_tag(tag)))
+                .map(|mut quest| {
+                    quest.input_loader.configure(&input);
+                    (label(&quest.info), quest)
+                })
+                .collect::<Vec<_>>();
+            if quests.is_empty() {
+                eprintln!("[Error] no quests found");
+                return ExitCode::FAILURE;
+            }
+            let reports = runner::execute_all(&quests, &config);
+            match config.format {
+                Format::Text => runner::print_summary(&reports),
+                _ => print_records(config.format, &reports),
+            }
+            if reports.iter().any(|r| r.has_failures()) {
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn print_records(format: Format, reports: &[runner::QuestReport]) {
+    if format != Format::Text {
+        println!("{}", export::render(format, &export::records(reports)));
+    }
+}
+
+fn select(registry: &QuestRegistry, selector: QuestSelector) -> Option<Quest> {
+    let quest = match selector {
+        QuestSelector::Exact { event, number } => registry.quest(event, number),
+        QuestSelector::Latest => registry.latest(),
+        QuestSelector::LatestSolved => registry.latest_solved(),
+    };
+    if quest.is_none() {
+        let available = registry
+            .events()
+            .iter()
+            .map(|(event, numbers)| match numbers.as_slice() {
+                [first, .., last] if last - first + 1 == numbers.len() => {
+                    format!("{} {}...{}", event, first, last)
+                }
+                _ => {
+                    let numbers = numbers.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+                    format!("{} {}", event, numbers.join(", "))
+                }
+            })
+            .collect::<Vec<_>>();
+        eprintln!(
+            "[Error] {} not found, available quests: {}",
+            describe(selector),
+            available.join("; ")
+        );
+    }
+    quest
 }
 
-fn execute(quest: &Quest) {
-    println!();
-    let mut description = quest.title.as_str();
-    if description.is_empty() {
-        description = "## UNTITLED QUEST ##";
+fn describe(selector: QuestSelector) -> String {
+    match selector {
+        QuestSelector::Exact { event, number } => format!("quest {} {}", event, number),
+        QuestSelector::Latest => "latest quest".to_string(),
+        QuestSelector::LatestSolved => "latest solved quest".to_string(),
     }
-    println!("{}", description);
-    let solution = &quest.solution;
-    for part in 0..3 {
-        let Ok(input) = quest.input_loader.load(part) else {
-            println!("[Error] failed to load input for part {}", part + 1);
-            continue;
-        };
-        let now = Instant::now();
-        let result = match part {
-            0 => solution.part_one(&input),
-            1 => solution.part_two(&input),
-            2 => solution.part_three(&input),
-            _ => format!("[Warn] part {} not found", part),
-        };
-        let duration = now.elapsed().as_millis();
-        let title = format!("{} ms for part {}", duration, part + 1);
-        println!("{:>30}: {}", title, result);
+}
+
+fn label(info: &QuestInfo) -> String {
+    format!("{:>4} {:>2}", info.event, info.number)
+}
+
+fn list(registry: &QuestRegistry, tag: Option<&str>) {
+    let infos = registry
+        .all()
+        .map(|quest| quest.info)
+        .filter(|info| tag.is_none_or(|tag| info.has_tag(tag)))
+        .collect::<Vec<_>>();
+    let width = infos
+        .iter()
+        .map(|info| info.name.chars().count())
+        .max()
+        .unwrap_or_default();
+    for (i, info) in infos.iter().enumerate() {
+        if i == 0 || infos[i - 1].event != info.event {
+            let name = event::spec(info.event).map_or("", |spec| spec.name);
+            println!("{}", format!("Event {}: {}", info.event, name).trim_end());
+        }
+        let difficulty = info
+            .difficulty
+            .map(|d| format!("{}/5", d))
+            .unwrap_or("-".to_string());
+        let line = format!(
+            "{}  {:<width$}  {:>3}  {}",
+            label(info),
+            info.name,
+            difficulty,
+            info.tags.join(", "),
+            width = width
+        );
+        println!("{}", line.trim_end());
     }
 }
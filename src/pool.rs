@@ -0,0 +1,77 @@
+use std::collections::BTreeMap;
+use std::sync::{mpsc, Mutex};
+use std::thread;
+
+/// Number of jobs that can run simultaneously on this machine
+pub fn available_jobs() -> usize {
+    thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+/// Runs jobs on a pool of `workers` threads and passes their results to `emit`
+/// in the order of the jobs, regardless of the order they finish in
+pub fn run_ordered<J, R, F, E>(workers: usize, jobs: Vec<J>, run: F, mut emit: E)
+where
+    J: Send,
+    R: Send,
+    F: Fn(J) -> R + Sync,
+    E: FnMut(usize, R),
+{
+    let workers = workers.clamp(1, jobs.len().max(1));
+    let queue = Mutex::new(jobs.into_iter().enumerate());
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let (queue, run) = (&queue, &run);
+            scope.spawn(move || loop {
+                let Some((index, job)) = queue.lock().unwrap().next() else {
+                    break;
+                };
+                if sender.send((index, run(job))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+        // results are held back until all previous jobs are emitted
+        let mut pending = BTreeMap::new();
+        let mut next = 0;
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&next) {
+                emit(next, result);
+                next += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn pool_run_ordered() {
+        // earlier jobs finish later
+        let jobs = (0..8u64).collect::<Vec<_>>();
+        let mut emitted = Vec::new();
+        run_ordered(
+            4,
+            jobs,
+            |job| {
+                thread::sleep(Duration::from_millis(8 - job));
+                job * 10
+            },
+            |index, result| emitted.push((index, result)),
+        );
+        let expected = (0..8).map(|i| (i as usize, i * 10)).collect::<Vec<_>>();
+        assert_eq!(emitted, expected);
+
+        let mut count = 0;
+        run_ordered(3, Vec::<u64>::new(), |job| job, |_, _| count += 1);
+        assert_eq!(count, 0);
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    BigInt(i128),
+    Text(String),
+    NotImplemented,
+}
+
+impl Answer {
+    pub fn as_integer(&self) -> Option<i128> {
+        match self {
+            Answer::Int(value) => Some(*value as i128),
+            Answer::BigInt(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Int(_) | Answer::BigInt(_) => "integer",
+            Answer::Text(_) => "text",
+            Answer::NotImplemented => "not implemented",
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(value) => write!(f, "{}", value),
+            Answer::BigInt(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+            Answer::NotImplemented => write!(f, "[not implemented]"),
+        }
+    }
+}
+
+macro_rules! impl_from_small_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Int(i64::from(value))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_from_large_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    if let Ok(value) = i64::try_from(value) {
+                        return Answer::Int(value);
+                    }
+                    match i128::try_from(value) {
+                        Ok(value) => Answer::BigInt(value),
+                        Err(_) => Answer::Text(value.to_string()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_small_integer!(i8, i16, i32, i64, u8, u16, u32);
+impl_from_large_integer!(i128, isize, u64, u128, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn answer_from_integers() {
+        assert_eq!(Answer::from(42u8), Answer::Int(42));
+        assert_eq!(Answer::from(-42i32), Answer::Int(-42));
+        assert_eq!(Answer::from(u64::MAX), Answer::BigInt(u64::MAX as i128));
+        assert_eq!(Answer::from(u128::MAX), Answer::Text(u128::MAX.to_string()));
+    }
+
+    #[test]
+    fn answer_display() {
+        assert_eq!(Answer::from(12usize).to_string(), "12");
+        assert_eq!(Answer::from("RR@").to_string(), "RR@");
+        assert_eq!(Answer::BigInt(1 << 70).as_integer(), Some(1 << 70));
+        assert_eq!(Answer::from("12").as_integer(), None);
+    }
+}
@@ -0,0 +1,249 @@
+use super::cancellation::CancellationToken;
+use super::SolveError;
+use crate::common::log::Level;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Messages kept per phase, later ones are only counted
+const MAX_LOGS: usize = 1000;
+/// Minimal time between two progress callbacks
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    pub level: Level,
+    /// Time since the start of the phase
+    pub elapsed: Duration,
+    pub message: String,
+}
+
+/// Completed units of work, `total` is `None` if it isn't known in advance
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    pub done: u64,
+    pub total: Option<u64>,
+}
+
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.total {
+            Some(total) if total > 0 => {
+                write!(f, "{}/{} ({}%)", self.done, total, self.done * 100 / total)
+            }
+            _ => write!(f, "{}", self.done),
+        }
+    }
+}
+
+/// Messages and counters collected while a part was parsed and solved
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Diagnostics {
+    pub logs: Vec<LogEntry>,
+    pub counters: BTreeMap<&'static str, u64>,
+    /// Messages over the limit
+    pub dropped: usize,
+}
+
+type ProgressCallback = Box<dyn Fn(Progress) + Send + Sync>;
+
+/// State of the running parse or solve phase. Solutions reach it with the functions of this
+/// module and the log macros, which do nothing with it outside of a phase
+pub struct SolveContext {
+    token: CancellationToken,
+    started: Instant,
+    diagnostics: Mutex<Diagnostics>,
+    /// Time of the last progress callback
+    progress_shown: Mutex<Option<Instant>>,
+    on_progress: Option<ProgressCallback>,
+}
+
+impl Default for SolveContext {
+    fn default() -> Self {
+        Self {
+            token: CancellationToken::new(),
+            started: Instant::now(),
+            diagnostics: Mutex::default(),
+            progress_shown: Mutex::default(),
+            on_progress: None,
+        }
+    }
+}
+
+impl SolveContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Context calling `on_progress` with the reported progress, at most every 100 ms
+    pub fn with_progress<F: Fn(Progress) + Send + Sync + 'static>(on_progress: F) -> Self {
+        Self {
+            on_progress: Some(Box::new(on_progress)),
+            ..Self::default()
+        }
+    }
+
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+
+    pub fn log<S: Into<String>>(&self, level: Level, message: S) {
+        let mut diagnostics = lock(&self.diagnostics);
+        if diagnostics.logs.len() >= MAX_LOGS {
+            diagnostics.dropped += 1;
+            return;
+        }
+        diagnostics.logs.push(LogEntry {
+            level,
+            elapsed: self.started.elapsed(),
+            message: message.into(),
+        });
+    }
+
+    /// Adds `value` to the named counter
+    pub fn count(&self, name: &'static str, value: u64) {
+        *lock(&self.diagnostics).counters.entry(name).or_default() += value;
+    }
+
+    pub fn progress(&self, done: u64, total: Option<u64>) {
+        let Some(on_progress) = &self.on_progress else {
+            return;
+        };
+        let mut shown = lock(&self.progress_shown);
+        if shown.is_some_and(|last| last.elapsed() < PROGRESS_INTERVAL) {
+            return;
+        }
+        *shown = Some(Instant::now());
+        drop(shown);
+        on_progress(Progress { done, total });
+    }
+
+    /// Whether the progress callback was called
+    pub fn progress_shown(&self) -> bool {
+        lock(&self.progress_shown).is_some()
+    }
+
+    pub fn diagnostics(&self) -> Diagnostics {
+        lock(&self.diagnostics).clone()
+    }
+}
+
+/// Solutions may panic while holding the lock, the collected data is still usable
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Arc<SolveContext>>> = const { RefCell::new(None) };
+}
+
+/// Makes the context and its cancellation token available to the closure
+pub fn with_context<T, F: FnOnce() -> T>(context: Arc<SolveContext>, f: F) -> T {
+    let previous = CURRENT.with(|current| current.replace(Some(context)));
+    let result = f();
+    CURRENT.with(|current| *current.borrow_mut() = previous);
+    result
+}
+
+fn with_current<F: FnOnce(&SolveContext)>(f: F) {
+    CURRENT.with(|current| {
+        if let Some(context) = current.borrow().as_deref() {
+            f(context);
+        }
+    });
+}
+
+/// Keeps the message with the running phase, `false` outside of a phase
+pub fn record(level: Level, message: &str) -> bool {
+    CURRENT.with(|current| match current.borrow().as_deref() {
+        Some(context) => {
+            context.log(level, message);
+            true
+        }
+        None => false,
+    })
+}
+
+/// Long running loops should poll it to stop cleanly when the runner gives up on the part
+pub fn check_cancelled() -> Result<(), SolveError> {
+    let cancelled = CURRENT.with(|current| {
+        current
+            .borrow()
+            .as_deref()
+            .is_some_and(|context| context.token.is_cancelled())
+    });
+    if cancelled {
+        return Err(SolveError::new("cancelled"));
+    }
+    Ok(())
+}
+
+/// Adds `value` to the named counter, e.g. iterations or visited states
+pub fn count(name: &'static str, value: u64) {
+    with_current(|context| context.count(name, value));
+}
+
+/// Reports `done` units of work out of `total`, long running parts should call it regularly
+pub fn progress(done: u64, total: Option<u64>) {
+    with_current(|context| context.progress(done, total));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    #[test]
+    fn context_collects() {
+        assert!(!record(Level::Info, "outside of a phase"));
+        let context = Arc::new(SolveContext::new());
+        with_context(context.clone(), || {
+            assert!(record(Level::Info, "cycle found"));
+            crate::warn!("round {}", 1);
+            // below the default info level
+            crate::debug!("round {}", 2);
+            count("rounds", 10);
+            count("rounds", 5);
+            assert!(check_cancelled().is_ok());
+            context.token().cancel();
+            assert!(check_cancelled().is_err());
+        });
+        count("rounds", 1);
+        let diagnostics = context.diagnostics();
+        let levels = diagnostics
+            .logs
+            .iter()
+            .map(|entry| (entry.level, entry.message.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            levels,
+            [(Level::Info, "cycle found"), (Level::Warn, "round 1")]
+        );
+        assert_eq!(diagnostics.counters[&"rounds"], 15);
+        assert!(check_cancelled().is_ok());
+    }
+
+    #[test]
+    fn context_progress() {
+        let last = Arc::new(AtomicU64::new(0));
+        let shown = last.clone();
+        let context = Arc::new(SolveContext::with_progress(move |progress| {
+            shown.store(progress.done, Ordering::Relaxed);
+        }));
+        with_context(context.clone(), || {
+            for done in 1..=100 {
+                progress(done, Some(100));
+            }
+        });
+        // calls are throttled, the first one passes
+        assert_eq!(last.load(Ordering::Relaxed), 1);
+        assert!(context.progress_shown());
+        let progress = Progress {
+            done: 25,
+            total: Some(100),
+        };
+        assert_eq!(progress.to_string(), "25/100 (25%)");
+    }
+}
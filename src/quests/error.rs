@@ -0,0 +1,155 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Input loading errors, `part` is 1-based part number
+#[derive(Debug)]
+pub enum QuestError {
+    NoInput {
+        part: usize,
+    },
+    NotFound {
+        path: String,
+        part: usize,
+    },
+    Io {
+        path: String,
+        part: usize,
+        source: std::io::Error,
+    },
+}
+
+impl Display for QuestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuestError::NoInput { part } => write!(f, "no input source for part {}", part),
+            QuestError::NotFound { path, part } => {
+                write!(f, "{} not found (input for part {})", path, part)
+            }
+            QuestError::Io { path, part, source } => write!(
+                f,
+                "failed to read {} (input for part {}): {}",
+                path, part, source
+            ),
+        }
+    }
+}
+
+impl Error for QuestError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            QuestError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputPosition {
+    pub line: usize,
+    pub column: Option<usize>,
+}
+
+#[derive(Debug)]
+pub struct SolveError {
+    pub message: String,
+    pub position: Option<InputPosition>,
+    pub source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl SolveError {
+    pub fn new<T: Into<String>>(message: T) -> Self {
+        Self {
+            message: message.into(),
+            position: None,
+            source: None,
+        }
+    }
+
+    /// Error position in the input, both line and column are 1-based
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.position = Some(InputPosition { line, column: None });
+        self
+    }
+
+    pub fn at(mut self, line: usize, column: usize) -> Self {
+        self.position = Some(InputPosition {
+            line,
+            column: Some(column),
+        });
+        self
+    }
+
+    pub fn with_source<E: Error + Send + Sync + 'static>(mut self, source: E) -> Self {
+        self.source = Some(Box::new(source));
+        self
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)?;
+        match self.position {
+            Some(InputPosition {
+                line,
+                column: Some(column),
+            }) => write!(f, " at line {}, column {}", line, column)?,
+            Some(InputPosition { line, column: None }) => write!(f, " at line {}", line)?,
+            None => {}
+        }
+        if let Some(source) = &self.source {
+            write!(f, ": {}", source)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for SolveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|err| err.as_ref() as &(dyn Error + 'static))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn quest_error_display() {
+        let err = QuestError::NotFound {
+            path: "input/everybody_codes_e2024_q08_p2.txt".to_string(),
+            part: 2,
+        };
+        assert_eq!(
+            err.to_string(),
+            "input/everybody_codes_e2024_q08_p2.txt not found (input for part 2)"
+        );
+        assert!(err.source().is_none());
+        let err = QuestError::Io {
+            path: "input".to_string(),
+            part: 1,
+            source: std::io::Error::other("is a directory"),
+        };
+        assert_eq!(
+            err.to_string(),
+            "failed to read input (input for part 1): is a directory"
+        );
+        assert!(err.source().is_some());
+    }
+
+    #[test]
+    fn solve_error_display() {
+        let source = "x".parse::<u32>().unwrap_err();
+        let err = SolveError::new("invalid number")
+            .at(3, 5)
+            .with_source(source);
+        assert_eq!(
+            err.to_string(),
+            "invalid number at line 3, column 5: invalid digit found in string"
+        );
+        assert!(err.source().is_some());
+        let err = SolveError::new("invalid format").at_line(2);
+        assert_eq!(err.to_string(), "invalid format at line 2");
+    }
+}
@@ -0,0 +1,139 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Yearly events are identified by their year, story events by their number
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Event {
+    Year(usize),
+    Story(usize),
+}
+
+impl Event {
+    /// Number used by everybody.codes in file names
+    pub fn id(&self) -> usize {
+        match self {
+            Event::Year(year) => *year,
+            Event::Story(number) => *number,
+        }
+    }
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // padding is applied to the whole identifier
+        let id = match self {
+            Event::Year(year) => year.to_string(),
+            Event::Story(number) => format!("s{}", number),
+        };
+        f.pad(&id)
+    }
+}
+
+impl FromStr for Event {
+    type Err = String;
+
+    /// Accepts years, e.g. `2024`, and story numbers, e.g. `s1` or `story1`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid event '{}', expected a year or s<N>", s);
+        match s.strip_prefix("story").or_else(|| s.strip_prefix('s')) {
+            Some(number) => number
+                .parse::<usize>()
+                .map(Event::Story)
+                .map_err(|_| invalid()),
+            None => s.parse::<usize>().map(Event::Year).map_err(|_| invalid()),
+        }
+    }
+}
+
+/// Event description with the naming conventions of its files.
+/// Patterns replace `{id}` with the event id, `{quest}` with the zero-padded quest number
+/// and `{part}` with the part number
+pub struct EventSpec {
+    pub event: Event,
+    pub name: &'static str,
+    pub input_pattern: &'static str,
+    pub answers_pattern: &'static str,
+}
+
+const DEFAULT_INPUT_PATTERN: &str = "everybody_codes_e{id}_q{quest}_p{part}.txt";
+
+const DEFAULT_ANSWERS_PATTERN: &str = "everybody_codes_e{id}_q{quest}.txt";
+
+/// Known events in release order, new events are appended
+pub const EVENTS: &[EventSpec] = &[
+    EventSpec {
+        event: Event::Year(2024),
+        name: "The Kingdom of Algorithmia",
+        input_pattern: DEFAULT_INPUT_PATTERN,
+        answers_pattern: DEFAULT_ANSWERS_PATTERN,
+    },
+    EventSpec {
+        event: Event::Story(1),
+        name: "Echoes of Enigmatus",
+        input_pattern: DEFAULT_INPUT_PATTERN,
+        answers_pattern: DEFAULT_ANSWERS_PATTERN,
+    },
+];
+
+pub fn spec(event: Event) -> Option<&'static EventSpec> {
+    EVENTS.iter().find(|spec| spec.event == event)
+}
+
+/// Position of the event in the release order, unknown events come after the known ones
+pub fn release_rank(event: Event) -> usize {
+    EVENTS
+        .iter()
+        .position(|spec| spec.event == event)
+        .unwrap_or(EVENTS.len())
+}
+
+/// File name of the part input, unknown events use the default naming
+pub fn input_file_name(event: Event, quest: usize, part: usize) -> String {
+    let pattern = spec(event).map_or(DEFAULT_INPUT_PATTERN, |s| s.input_pattern);
+    file_name(pattern, event, quest).replace("{part}", &part.to_string())
+}
+
+pub fn answers_file_name(event: Event, quest: usize) -> String {
+    let pattern = spec(event).map_or(DEFAULT_ANSWERS_PATTERN, |s| s.answers_pattern);
+    file_name(pattern, event, quest)
+}
+
+fn file_name(pattern: &str, event: Event, quest: usize) -> String {
+    pattern
+        .replace("{id}", &event.id().to_string())
+        .replace("{quest}", &format!("{:02}", quest))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn event_parse() {
+        assert_eq!("2024".parse(), Ok(Event::Year(2024)));
+        assert_eq!("s1".parse(), Ok(Event::Story(1)));
+        assert_eq!("story2".parse(), Ok(Event::Story(2)));
+        assert!("s".parse::<Event>().is_err());
+        assert!("y2024".parse::<Event>().is_err());
+        assert_eq!(format!("{:>4}", Event::Story(1)), "  s1");
+    }
+
+    #[test]
+    fn event_release_rank() {
+        // story events may be released after later yearly events are announced
+        assert!(release_rank(Event::Year(2024)) < release_rank(Event::Story(1)));
+        assert_eq!(release_rank(Event::Year(1999)), EVENTS.len());
+    }
+
+    #[test]
+    fn event_file_names() {
+        assert_eq!(
+            input_file_name(Event::Year(2024), 7, 3),
+            "everybody_codes_e2024_q07_p3.txt"
+        );
+        assert_eq!(
+            answers_file_name(Event::Story(1), 12),
+            "everybody_codes_e1_q12.txt"
+        );
+    }
+}
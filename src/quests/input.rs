@@ -0,0 +1,302 @@
+use super::event::{self, Event};
+use super::{Part, QuestError, QuestResult};
+use crate::common::strings::normalized;
+use std::collections::HashSet;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Environment variable with the directory of the puzzle input files
+pub const INPUT_DIR_ENV: &str = "KOA_INPUT_DIR";
+
+const DEFAULT_INPUT_DIR: &str = "input";
+
+const ANSWERS_DIR: &str = "answers";
+
+const STDIN_NAME: &str = "<stdin>";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+}
+
+/// Overrides of the default input locations, `parts` holds 1-based part numbers
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InputOptions {
+    pub input_dir: Option<PathBuf>,
+    pub parts: Vec<(usize, InputSource)>,
+}
+
+impl InputOptions {
+    /// Input directory from the environment if it isn't set explicitly
+    pub fn with_env(mut self) -> Self {
+        if self.input_dir.is_none() {
+            self.input_dir = std::env::var_os(INPUT_DIR_ENV).map(PathBuf::from);
+        }
+        self
+    }
+}
+
+pub struct QuestInputLoader {
+    input_dir: PathBuf,
+    // default file names by the event conventions
+    naming: Option<(Event, usize)>,
+    sources: Vec<Option<InputSource>>,
+    answers: Option<PathBuf>,
+    // zero-based indices of the parts with explicitly specified input
+    custom_parts: HashSet<usize>,
+}
+
+impl QuestInputLoader {
+    /// Input and answers files named by the conventions of the event
+    pub fn with_quest_id(event: Event, number: usize) -> Self {
+        let answers = format!(
+            "{}/{}",
+            ANSWERS_DIR,
+            event::answers_file_name(event, number)
+        );
+        let mut loader = Self::with_sources::<&str>(&[]).with_answers(answers);
+        loader.input_dir = PathBuf::from(DEFAULT_INPUT_DIR);
+        loader.naming = Some((event, number));
+        loader
+    }
+
+    pub fn with_sources<T: AsRef<str>>(path_list: &[T]) -> Self {
+        let sources = path_list
+            .iter()
+            .map(|s| Some(InputSource::File(PathBuf::from(s.as_ref()))))
+            .collect::<Vec<_>>();
+        Self {
+            input_dir: PathBuf::new(),
+            naming: None,
+            sources,
+            answers: None,
+            custom_parts: HashSet::new(),
+        }
+    }
+
+    pub fn with_answers<T: AsRef<str>>(mut self, path: T) -> Self {
+        self.answers = Some(PathBuf::from(path.as_ref()));
+        self
+    }
+
+    /// Applies input overrides. Answers are expected in the `answers` directory next to
+    /// the input directory, parts with explicit input have no known answers
+    pub fn configure(&mut self, options: &InputOptions) {
+        if let Some(dir) = &options.input_dir {
+            self.input_dir = dir.clone();
+            if let Some(file_name) = self.answers.as_ref().and_then(|p| p.file_name()) {
+                let parent = dir.parent().unwrap_or(Path::new(""));
+                self.answers = Some(parent.join(ANSWERS_DIR).join(file_name));
+            }
+        }
+        for (part, source) in &options.parts {
+            let index = part - 1;
+            if index >= self.sources.len() {
+                self.sources.resize(index + 1, None);
+            }
+            // explicit files are relative to the working directory rather than the input one
+            let source = match source {
+                InputSource::File(path) if path.is_relative() => std::env::current_dir()
+                    .map(|dir| InputSource::File(dir.join(path)))
+                    .unwrap_or(source.clone()),
+                _ => source.clone(),
+            };
+            self.sources[index] = Some(source);
+            self.custom_parts.insert(index);
+        }
+    }
+
+    /// Accepted answers, one line per part. Empty lines or '?' mark unknown answers,
+    /// missing answers file means that all answers are unknown
+    pub fn load_answers(&self) -> Vec<Option<String>> {
+        let Some(file) = &self.answers else {
+            return Vec::new();
+        };
+        let Ok(content) = std::fs::read_to_string(resolve(file)) else {
+            return Vec::new();
+        };
+        content
+            .lines()
+            .map(|s| s.trim())
+            .enumerate()
+            .map(|(i, s)| match s {
+                _ if self.custom_parts.contains(&i) => None,
+                "" | "?" => None,
+                _ => Some(s.to_string()),
+            })
+            .collect()
+    }
+
+    /// Loads the input of the part with normalized line endings. Explicitly configured input
+    /// of the part takes precedence over the input file it shares with another part
+    pub fn load(&self, part: &Part) -> QuestResult<String> {
+        self.load_raw(self.input_index(part), part.number)
+            .map(|input| normalized(&input))
+    }
+
+    /// Zero-based index of the input read by the part, parts with the same index share input
+    pub fn input_index(&self, part: &Part) -> usize {
+        if self.custom_parts.contains(&part.index()) {
+            part.index()
+        } else {
+            part.input - 1
+        }
+    }
+
+    /// Resolved path of the input file read by the part, `None` for stdin
+    pub fn input_path(&self, part: &Part) -> Option<PathBuf> {
+        match self.source(self.input_index(part))? {
+            InputSource::File(file) => Some(resolve(&self.input_dir.join(file))),
+            InputSource::Stdin => None,
+        }
+    }
+
+    fn source(&self, index: usize) -> Option<InputSource> {
+        if let Some(Some(source)) = self.sources.get(index) {
+            return Some(source.clone());
+        }
+        let (event, number) = self.naming?;
+        let name = event::input_file_name(event, number, index + 1);
+        Some(InputSource::File(PathBuf::from(name)))
+    }
+
+    fn load_raw(&self, index: usize, part: usize) -> QuestResult<String> {
+        let Some(source) = self.source(index) else {
+            return Err(QuestError::NoInput { part });
+        };
+        let file = match source {
+            InputSource::File(file) => resolve(&self.input_dir.join(file)),
+            InputSource::Stdin => {
+                return read_stdin().map_err(|source| QuestError::Io {
+                    path: STDIN_NAME.to_string(),
+                    part,
+                    source,
+                })
+            }
+        };
+        let input = std::fs::read_to_string(&file).map_err(|source| {
+            let path = file.display().to_string();
+            match source.kind() {
+                std::io::ErrorKind::NotFound => QuestError::NotFound { path, part },
+                _ => QuestError::Io { path, part, source },
+            }
+        })?;
+        Ok(input)
+    }
+}
+
+/// Relative paths that don't exist in the working directory are resolved from the crate root,
+/// so the binary can be launched from any directory of the checkout
+fn resolve(path: &Path) -> PathBuf {
+    if path.is_absolute() || path.exists() {
+        return path.to_path_buf();
+    }
+    let from_root = Path::new(env!("CARGO_MANIFEST_DIR")).join(path);
+    if from_root.exists() {
+        return from_root;
+    }
+    path.to_path_buf()
+}
+
+/// Stdin is read once and shared between all parts that use it
+fn read_stdin() -> std::io::Result<String> {
+    static STDIN: OnceLock<Result<String, String>> = OnceLock::new();
+    STDIN
+        .get_or_init(|| {
+            let mut input = String::new();
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .map(|_| input)
+                .map_err(|err| err.to_string())
+        })
+        .clone()
+        .map_err(std::io::Error::other)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn input_loader_configure() {
+        let mut loader = QuestInputLoader::with_quest_id(Event::Year(2024), 1);
+        assert!(loader.load(&Part::new(1)).is_ok());
+        assert!(loader.load_answers()[1].is_some());
+        let options = InputOptions {
+            input_dir: Some(PathBuf::from("missing/input")),
+            parts: vec![(
+                2,
+                InputSource::File(PathBuf::from("input/everybody_codes_e2024_q01_p1.txt")),
+            )],
+        };
+        loader.configure(&options);
+        let Err(QuestError::NotFound { path, part }) = loader.load(&Part::new(1)) else {
+            panic!("input must not be found");
+        };
+        assert_eq!(path, "missing/input/everybody_codes_e2024_q01_p1.txt");
+        assert_eq!(part, 1);
+        assert!(loader.load(&Part::new(2)).is_ok());
+        // missing input of the shared file is reported for the part
+        assert!(matches!(
+            loader.load(&Part {
+                number: 3,
+                input: 1
+            }),
+            Err(QuestError::NotFound { part: 3, .. })
+        ));
+        // explicit input of the part is used instead of the shared file
+        assert!(loader
+            .load(&Part {
+                number: 2,
+                input: 1
+            })
+            .is_ok());
+        // answers are looked up next to the missing input directory
+        assert!(loader.load_answers().is_empty());
+
+        let mut loader = QuestInputLoader::with_quest_id(Event::Year(2024), 1);
+        let options = InputOptions {
+            input_dir: None,
+            parts: vec![(2, InputSource::Stdin)],
+        };
+        loader.configure(&options);
+        let answers = loader.load_answers();
+        assert!(answers[0].is_some());
+        assert!(answers[1].is_none());
+    }
+
+    #[test]
+    fn input_loader_sparse_sources() {
+        let mut loader = QuestInputLoader::with_sources(&["a.txt"]);
+        loader.configure(&InputOptions {
+            input_dir: None,
+            parts: vec![(3, InputSource::File(PathBuf::from("b.txt")))],
+        });
+        assert!(matches!(
+            loader.load(&Part::new(1)),
+            Err(QuestError::NotFound { part: 1, .. })
+        ));
+        assert!(matches!(
+            loader.load(&Part::new(2)),
+            Err(QuestError::NoInput { part: 2 })
+        ));
+        assert!(matches!(
+            loader.load(&Part::new(3)),
+            Err(QuestError::NotFound { part: 3, .. })
+        ));
+        assert!(loader.input_path(&Part::new(2)).is_none());
+        assert!(loader.input_path(&Part::new(3)).unwrap().ends_with("b.txt"));
+    }
+
+    #[test]
+    fn input_loader_normalize() {
+        let path = std::env::temp_dir().join("koa_input_loader_normalize.txt");
+        std::fs::write(&path, "\u{feff}A:+,-\r\nB:=,+\r\n\r\n").unwrap();
+        let sources = [path.display().to_string()];
+        let loader = QuestInputLoader::with_sources(&sources);
+        assert_eq!(loader.load(&Part::new(1)).unwrap(), "A:+,-\nB:=,+\n");
+        std::fs::remove_file(path).ok();
+    }
+}
@@ -1,3 +1,9 @@
+mod answer;
+pub mod cancellation;
+pub mod context;
+mod error;
+pub mod event;
+mod input;
 mod quest_2024_01;
 mod quest_2024_02;
 mod quest_2024_03;
@@ -5,75 +11,325 @@ mod quest_2024_04;
 mod quest_2024_05;
 mod quest_2024_06;
 mod quest_2024_07;
+mod typed;
 
-pub struct QuestFactory;
-impl QuestFactory {
-    pub fn quest(&self, number: usize) -> Option<Quest> {
-        [
-            quest_2024_01::assemble,
-            quest_2024_02::assemble,
-            quest_2024_03::assemble,
-            quest_2024_04::assemble,
-            quest_2024_05::assemble,
-            quest_2024_06::assemble,
-            quest_2024_07::assemble,
-        ]
-        .get(number - 1)
-        .map(|f| f())
-    }
-
-    pub fn custom(&self) -> Option<Quest> {
-        self.quest(7)
-    }
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+pub use answer::Answer;
+pub use error::{QuestError, SolveError};
+pub use event::Event;
+pub use input::{InputOptions, InputSource, QuestInputLoader};
+pub use typed::{Typed, TypedSolution};
+
+/// Quest modules of all events, the registry orders them by event and quest number
+const ASSEMBLERS: &[fn() -> Quest] = &[
+    quest_2024_01::assemble,
+    quest_2024_02::assemble,
+    quest_2024_03::assemble,
+    quest_2024_04::assemble,
+    quest_2024_05::assemble,
+    quest_2024_06::assemble,
+    quest_2024_07::assemble,
+];
+
+/// Quests ordered by the release of their events, then by quest number
+type ReleaseKey = (usize, Event, usize);
+
+pub struct QuestRegistry {
+    assemblers: BTreeMap<ReleaseKey, fn() -> Quest>,
 }
 
-#[derive(Debug)]
-pub enum QuestError {
-    IoError,
-    NoInput,
+impl QuestRegistry {
+    pub fn new() -> Self {
+        Self::with_assemblers(ASSEMBLERS)
+    }
+
+    fn with_assemblers(assemblers: &[fn() -> Quest]) -> Self {
+        let assemblers = assemblers
+            .iter()
+            .map(|assemble| {
+                let info = assemble().info;
+                (
+                    (event::release_rank(info.event), info.event, info.number),
+                    *assemble,
+                )
+            })
+            .collect();
+        Self { assemblers }
+    }
+
+    pub fn quest(&self, event: Event, number: usize) -> Option<Quest> {
+        let key = (event::release_rank(event), event, number);
+        self.assemblers.get(&key).map(|f| f())
+    }
+
+    /// The last quest of the last released event
+    pub fn latest(&self) -> Option<Quest> {
+        self.assemblers.values().last().map(|f| f())
+    }
+
+    /// The last quest with recorded answers
+    pub fn latest_solved(&self) -> Option<Quest> {
+        self.all().rev().find(|quest| {
+            quest
+                .input_loader
+                .load_answers()
+                .iter()
+                .any(|answer| answer.is_some())
+        })
+    }
+
+    pub fn all(&self) -> impl DoubleEndedIterator<Item = Quest> + '_ {
+        self.assemblers.values().map(|f| f())
+    }
+
+    /// Events with registered quests and their quest numbers, in release order
+    pub fn events(&self) -> Vec<(Event, Vec<usize>)> {
+        let mut events = Vec::<(Event, Vec<usize>)>::new();
+        for (_, event, number) in self.assemblers.keys() {
+            match events.last_mut() {
+                Some((last, numbers)) if last == event => numbers.push(*number),
+                _ => events.push((*event, vec![*number])),
+            }
+        }
+        events
+    }
 }
 
 pub type QuestResult<T> = Result<T, QuestError>;
 
 pub struct Quest {
-    pub title: String,
+    pub info: QuestInfo,
     pub input_loader: QuestInputLoader,
-    pub solution: Box<dyn Solution>,
+    pub solution: Arc<dyn Solution>,
+    pub examples: &'static [Example],
+    /// Source of the quest module, cached answers are keyed by it
+    pub source: &'static str,
 }
 
-pub trait Solution {
-    fn part_one(&self, input: &str) -> String;
-    fn part_two(&self, input: &str) -> String;
-    fn part_three(&self, input: &str) -> String;
+/// Source of the calling module embedded at compile time
+#[macro_export]
+macro_rules! quest_source {
+    () => {
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", file!()))
+    };
 }
 
-pub struct QuestInputLoader {
-    sources: Vec<String>,
+/// Quest identity and description used for titles, listing and filtering
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuestInfo {
+    pub event: Event,
+    pub number: usize,
+    pub name: &'static str,
+    /// Algorithm categories, lowercase
+    pub tags: &'static [&'static str],
+    /// Subjective rating from 1 (easy) to 5 (hard)
+    pub difficulty: Option<u8>,
 }
 
-impl QuestInputLoader {
-    pub fn with_quest_date(year: usize, number: usize) -> Self {
-        let path_list = (1..=3)
-            .map(|part| format!("everybody_codes_e{}_q{:02}_p{}.txt", year, number, part))
-            // .inspect(|p| println!("{p}"))
-            .map(|s| format!("input/{}", s))
-            .collect::<Vec<_>>();
-        Self::with_sources(&path_list)
+impl QuestInfo {
+    pub fn title(&self) -> String {
+        let name = if self.name.is_empty() {
+            "## UNTITLED QUEST ##"
+        } else {
+            self.name
+        };
+        format!("Quest {} {}: {}", self.event, self.number, name)
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+}
+
+/// Worked example from the puzzle description, `part` is 1-based
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Example {
+    pub part: usize,
+    pub input: &'static str,
+    pub expected: &'static str,
+}
+
+pub type SolveResult = Result<Answer, SolveError>;
+
+/// Declared quest part. `input` is the 1-based number of the part whose input file is read,
+/// so several parts may share one file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Part {
+    pub number: usize,
+    pub input: usize,
+}
+
+impl Part {
+    /// Part with its own input file
+    pub const fn new(number: usize) -> Self {
+        Self {
+            number,
+            input: number,
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        self.number - 1
+    }
+}
+
+pub const DEFAULT_PARTS: &[Part] = &[Part::new(1), Part::new(2), Part::new(3)];
+
+/// Parsed input, shared between the parts that read the same input
+pub type ParsedInput = Arc<dyn Any + Send + Sync>;
+
+pub trait Solution: Send + Sync {
+    /// Parts in the order they are solved
+    fn parts(&self) -> &'static [Part] {
+        DEFAULT_PARTS
+    }
+
+    fn part_one(&self, _input: &str) -> SolveResult {
+        Ok(Answer::NotImplemented)
+    }
+
+    fn part_two(&self, _input: &str) -> SolveResult {
+        Ok(Answer::NotImplemented)
+    }
+
+    fn part_three(&self, _input: &str) -> SolveResult {
+        Ok(Answer::NotImplemented)
     }
 
-    pub fn with_sources<T: AsRef<str>>(path_list: &[T]) -> Self {
-        let sources = path_list
+    /// Solves part by its zero-based index, solutions with more than three parts override it
+    fn solve(&self, part: usize, input: &str) -> SolveResult {
+        match part {
+            0 => self.part_one(input),
+            1 => self.part_two(input),
+            2 => self.part_three(input),
+            _ => Err(SolveError::new(format!("part {} not found", part + 1))),
+        }
+    }
+
+    /// Parse phase, solutions without it keep the text input
+    fn parse(&self, input: &str) -> Result<ParsedInput, SolveError> {
+        Ok(Arc::new(input.to_string()))
+    }
+
+    /// Solves part by its zero-based index with the input produced by `parse`
+    fn solve_parsed(&self, part: usize, input: &ParsedInput) -> SolveResult {
+        let input = downcast::<String>(input)?;
+        self.solve(part, input)
+    }
+}
+
+fn downcast<T: 'static>(input: &ParsedInput) -> Result<&T, SolveError> {
+    (**input)
+        .downcast_ref::<T>()
+        .ok_or_else(|| SolveError::new("unexpected parsed input type"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::runner::Verdict;
+
+    #[test]
+    fn quest_info() {
+        for quest in QuestRegistry::new().all() {
+            assert!(event::spec(quest.info.event).is_some());
+            assert!(!quest.info.name.is_empty());
+        }
+        let info = QuestRegistry::new()
+            .quest(Event::Year(2024), 7)
+            .unwrap()
+            .info;
+        assert_eq!(info.title(), "Quest 2024 7: Not Fast but Furious");
+        assert!(info.has_tag("Simulation"));
+    }
+
+    #[test]
+    fn quest_registry() {
+        let registry = QuestRegistry::new();
+        let infos = ASSEMBLERS
             .iter()
-            .map(|s| s.as_ref().to_string())
-            .collect::<Vec<String>>();
-        Self { sources }
+            .map(|assemble| assemble().info)
+            .collect::<Vec<_>>();
+        // every quest has its own key
+        assert_eq!(registry.all().count(), infos.len());
+        for info in &infos {
+            let quest = registry.quest(info.event, info.number).unwrap();
+            assert_eq!(quest.info, *info);
+            // the source is embedded from the module of the quest
+            assert!(quest.source.contains(info.name));
+        }
+        assert!(registry.quest(Event::Year(2024), 0).is_none());
+
+        let events = registry.events();
+        let ranks = events
+            .iter()
+            .map(|(event, _)| event::release_rank(*event))
+            .collect::<Vec<_>>();
+        assert!(ranks.windows(2).all(|pair| pair[0] < pair[1]));
+        let listed = events
+            .iter()
+            .map(|(_, numbers)| numbers.len())
+            .sum::<usize>();
+        assert_eq!(listed, infos.len());
+
+        let latest = registry.latest().unwrap().info;
+        let (last_event, numbers) = events.last().unwrap();
+        assert_eq!(latest.event, *last_event);
+        assert_eq!(latest.number, *numbers.last().unwrap());
+        assert!(registry.latest_solved().is_some());
     }
 
-    pub fn load(&self, part: usize) -> QuestResult<String> {
-        let Some(file) = self.sources.get(part) else {
-            return Err(QuestError::NoInput);
-        };
-        let input = std::fs::read_to_string(file).map_err(|_| QuestError::IoError)?;
-        Ok(input)
+    fn story_quest() -> Quest {
+        struct Story;
+        impl Solution for Story {}
+        Quest {
+            info: QuestInfo {
+                event: Event::Story(1),
+                number: 1,
+                name: "Story",
+                tags: &[],
+                difficulty: None,
+            },
+            input_loader: QuestInputLoader::with_quest_id(Event::Story(1), 1),
+            solution: Arc::new(Story),
+            examples: &[],
+            source: "",
+        }
+    }
+
+    #[test]
+    fn quest_registry_release_order() {
+        // a scaffolded story quest becomes the latest one, yearly quests keep their order
+        let registry = QuestRegistry::with_assemblers(&[story_quest, quest_2024_01::assemble]);
+        let events = registry
+            .events()
+            .into_iter()
+            .map(|(event, _)| event)
+            .collect::<Vec<_>>();
+        assert_eq!(events, [Event::Year(2024), Event::Story(1)]);
+        assert_eq!(registry.latest().unwrap().info.event, Event::Story(1));
+        assert!(registry.quest(Event::Story(1), 1).is_some());
+    }
+
+    /// Solves the declared examples of the quest, each quest module checks its own examples
+    pub fn check_examples(quest: Quest) {
+        assert!(!quest.examples.is_empty(), "no examples declared");
+        let failures = quest
+            .examples
+            .iter()
+            .filter_map(|example| {
+                let answer = match quest.solution.solve(example.part - 1, example.input) {
+                    Ok(answer) => answer,
+                    Err(err) => return Some(format!("part {}: {}", example.part, err)),
+                };
+                let verdict = Verdict::check(&answer, Some(example.expected));
+                (verdict != Verdict::Pass)
+                    .then(|| format!("part {}: {} ({})", example.part, answer, verdict))
+            })
+            .collect::<Vec<_>>();
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
     }
 }
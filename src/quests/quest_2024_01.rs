@@ -1,31 +1,60 @@
-use crate::quests::{Quest, QuestInputLoader, Solution};
+use crate::quests::{Event, Example, Quest, QuestInfo, QuestInputLoader, Solution, SolveResult};
 use std::collections::HashMap;
+use std::sync::Arc;
+
+const INFO: QuestInfo = QuestInfo {
+    event: Event::Year(2024),
+    number: 1,
+    name: "The Battle for the Farmlands",
+    tags: &["arithmetic"],
+    difficulty: Some(1),
+};
 
 pub fn assemble() -> Quest {
     Quest {
-        title: "Quest 1: The Battle for the Farmlands".to_string(),
-        input_loader: QuestInputLoader::with_quest_date(2024, 1),
-        solution: Box::new(Q2024_1),
+        info: INFO,
+        input_loader: QuestInputLoader::with_quest_id(INFO.event, INFO.number),
+        solution: Arc::new(Q2024_1),
+        examples: EXAMPLES,
+        source: crate::quest_source!(),
     }
 }
 
+const EXAMPLES: &[Example] = &[
+    Example {
+        part: 1,
+        input: "ABBAC",
+        expected: "5",
+    },
+    Example {
+        part: 2,
+        input: "AxBCDDCAxD",
+        expected: "28",
+    },
+    Example {
+        part: 3,
+        input: "xBxAAABCDxCC",
+        expected: "30",
+    },
+];
+
 struct Q2024_1;
 
 impl Solution for Q2024_1 {
-    fn part_one(&self, input: &str) -> String {
-        calculate_potion_amount(input, 1)
+    fn part_one(&self, input: &str) -> SolveResult {
+        Ok(calculate_potion_amount(input, 1).into())
     }
 
-    fn part_two(&self, input: &str) -> String {
-        calculate_potion_amount(input, 2)
+    fn part_two(&self, input: &str) -> SolveResult {
+        Ok(calculate_potion_amount(input, 2).into())
     }
 
-    fn part_three(&self, input: &str) -> String {
-        calculate_potion_amount(input, 3)
+    fn part_three(&self, input: &str) -> SolveResult {
+        Ok(calculate_potion_amount(input, 3).into())
     }
 }
 
-fn calculate_potion_amount(input: &str, enemies: usize) -> String {
+fn calculate_potion_amount(input: &str, enemies: usize) -> i32 {
     let map = HashMap::from([('A', 0), ('B', 1), ('C', 3), ('D', 5)]);
     input
         .chars()
@@ -44,5 +73,15 @@ fn calculate_potion_amount(input: &str, enemies: usize) -> String {
                 }
         })
         .sum::<i32>()
-        .to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::quests::test::check_examples;
+
+    #[test]
+    fn quest2024_01_examples() {
+        check_examples(assemble());
+    }
 }
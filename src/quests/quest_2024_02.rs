@@ -1,45 +1,99 @@
 use crate::common::strings::{reversed_str, TrimmedSplit};
 use crate::common::Direction;
-use crate::quests::{Quest, QuestInputLoader, Solution};
+use crate::quests::{
+    Event, Example, Quest, QuestInfo, QuestInputLoader, Solution, SolveError, SolveResult,
+};
 use std::collections::HashSet;
+use std::sync::Arc;
+
+const INFO: QuestInfo = QuestInfo {
+    event: Event::Year(2024),
+    number: 2,
+    name: "The Runes of Power",
+    tags: &["strings", "grid"],
+    difficulty: Some(2),
+};
 
 pub fn assemble() -> Quest {
     Quest {
-        title: "Quest 2: The Runes of Power".to_string(),
-        input_loader: QuestInputLoader::with_quest_date(2024, 2),
-        solution: Box::new(Q2024_2),
+        info: INFO,
+        input_loader: QuestInputLoader::with_quest_id(INFO.event, INFO.number),
+        solution: Arc::new(Q2024_2),
+        examples: EXAMPLES,
+        source: crate::quest_source!(),
     }
 }
 
+const EXAMPLES: &[Example] = &[
+    Example {
+        part: 1,
+        input: "WORDS:THE,OWE,MES,ROD,HER
+
+AWAKEN THE POWER ADORNED WITH THE FLAMES BRIGHT IRE",
+        expected: "4",
+    },
+    Example {
+        part: 2,
+        input: "WORDS:THE,OWE,MES,ROD,HER,QAQ
+
+AWAKEN THE POWE ADORNED WITH THE FLAMES BRIGHT IRE
+THE FLAME SHIELDED THE HEART OF THE KINGS
+POWE PO WER P OWE R
+THERE IS THE END
+QAQAQ",
+        expected: "42",
+    },
+    Example {
+        part: 3,
+        input: "WORDS:THE,OWE,MES,ROD,RODEO
+
+HELWORLT
+ENIGWDXL
+TRODEOAL",
+        expected: "10",
+    },
+];
+
 struct Q2024_2;
 
 impl Solution for Q2024_2 {
-    fn part_one(&self, input: &str) -> String {
+    fn part_one(&self, input: &str) -> SolveResult {
         let data = split_input(input);
-        assert_eq!(data.len(), 2);
-        words_count(&data[0], &data[1]).to_string()
+        let [words, text] = data.as_slice() else {
+            return Err(SolveError::new(format!(
+                "expected words and text lines, found {} lines",
+                data.len()
+            )));
+        };
+        Ok(words_count(words, text)?.into())
     }
 
-    fn part_two(&self, input: &str) -> String {
+    fn part_two(&self, input: &str) -> SolveResult {
         let data = split_input(input);
-        let words = split_words(&data[0]);
-        assert!(data.len() > 1);
-        data[1..]
+        if data.len() < 2 {
+            return Err(SolveError::new(
+                "expected words line followed by text lines",
+            ));
+        }
+        let words = split_words(&data[0])?;
+        Ok(data[1..]
             .iter()
             .map(|text| symbols_count(&words, text))
             .sum::<usize>()
-            .to_string()
+            .into())
     }
 
-    fn part_three(&self, input: &str) -> String {
-        assert!(!input.is_empty());
-        let mut matrix: Vec<Vec<char>> = Vec::new();
+    fn part_three(&self, input: &str) -> SolveResult {
         let data = split_input(input);
-        for row in &data[1..] {
+        let Some((header, rows)) = data.split_first() else {
+            return Err(SolveError::new("empty input"));
+        };
+        let mut matrix: Vec<Vec<char>> = Vec::new();
+        for row in rows {
             let arr = row.chars().collect::<Vec<char>>();
             matrix.push(arr);
         }
-        let words = split_words(&data[0])
+        let words = split_words(header)?
             .iter()
             .map(|word| word.trim().chars().collect::<Vec<char>>())
             .collect::<Vec<Vec<char>>>();
@@ -65,7 +119,7 @@ impl Solution for Q2024_2 {
             }
         }
 
-        scales.len().to_string()
+        Ok(scales.len().into())
     }
 }
 
@@ -104,18 +158,19 @@ fn split_input(input: &str) -> Vec<String> {
         .collect::<Vec<String>>()
 }
 
-fn split_words(line: &str) -> Vec<&str> {
+fn split_words(line: &str) -> Result<Vec<&str>, SolveError> {
     let Some((_, words)) = line.split_once(":") else {
-        panic!("Invalid format");
+        return Err(SolveError::new("invalid words format, expected 'WORDS:<list>'").at_line(1));
     };
-    words.split(",").collect::<Vec<&str>>()
+    Ok(words.split(",").collect::<Vec<&str>>())
 }
 
-fn words_count(words: &str, text: &str) -> usize {
-    split_words(words)
+fn words_count(words: &str, text: &str) -> Result<usize, SolveError> {
+    let count = split_words(words)?
         .iter()
         .map(|word| text.match_indices(word).count())
-        .sum::<usize>()
+        .sum::<usize>();
+    Ok(count)
 }
 
 fn symbols_count(words: &[&str], text: &str) -> usize {
@@ -143,36 +198,12 @@ fn symbols_count(words: &[&str], text: &str) -> usize {
 }
 
 #[cfg(test)]
-mod tests {
+mod test {
     use super::*;
+    use crate::quests::test::check_examples;
 
     #[test]
-    fn quest2_case_1() {
-        let input = r"WORDS:THE,OWE,MES,ROD,HER
-
-AWAKEN THE POWER ADORNED WITH THE FLAMES BRIGHT IRE";
-        assert_eq!(Q2024_2.part_one(input), "4");
-    }
-
-    #[test]
-    fn quest2_case_2() {
-        let input = r"WORDS:THE,OWE,MES,ROD,HER,QAQ
-
-AWAKEN THE POWE ADORNED WITH THE FLAMES BRIGHT IRE
-THE FLAME SHIELDED THE HEART OF THE KINGS
-POWE PO WER P OWE R
-THERE IS THE END
-QAQAQ";
-        assert_eq!(Q2024_2.part_two(input), "42");
-    }
-
-    #[test]
-    fn quest2_case_3() {
-        let input = r"WORDS:THE,OWE,MES,ROD,RODEO
-
-HELWORLT
-ENIGWDXL
-TRODEOAL";
-        assert_eq!(Q2024_2.part_three(input), "10");
+    fn quest2024_02_examples() {
+        check_examples(assemble());
     }
 }
@@ -1,31 +1,67 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, sync::Arc};
 
 use crate::{
     common::Position2,
-    quests::{Quest, QuestInputLoader, Solution},
+    quests::{Event, Example, Quest, QuestInfo, QuestInputLoader, Solution, SolveResult},
+};
+
+const INFO: QuestInfo = QuestInfo {
+    event: Event::Year(2024),
+    number: 3,
+    name: "Mining Maestro",
+    tags: &["grid", "erosion"],
+    difficulty: Some(2),
 };
 
 pub fn assemble() -> Quest {
     Quest {
-        title: "Quest 3: Mining Maestro".to_string(),
-        input_loader: QuestInputLoader::with_quest_date(2024, 3),
-        solution: Box::new(Q2024_3),
+        info: INFO,
+        input_loader: QuestInputLoader::with_quest_id(INFO.event, INFO.number),
+        solution: Arc::new(Q2024_3),
+        examples: EXAMPLES,
+        source: crate::quest_source!(),
     }
 }
 
+const EXAMPLE: &str = "..........
+..###.##..
+...####...
+..######..
+..######..
+...####...
+..........";
+
+const EXAMPLES: &[Example] = &[
+    Example {
+        part: 1,
+        input: EXAMPLE,
+        expected: "35",
+    },
+    Example {
+        part: 2,
+        input: EXAMPLE,
+        expected: "35",
+    },
+    Example {
+        part: 3,
+        input: EXAMPLE,
+        expected: "29",
+    },
+];
+
 struct Q2024_3;
 
 impl Solution for Q2024_3 {
-    fn part_one(&self, input: &str) -> String {
+    fn part_one(&self, input: &str) -> SolveResult {
         let directions = [(1, 0), (-1, 0), (0, 1), (0, -1)];
-        blocks_count(input, &directions).to_string()
+        Ok(blocks_count(input, &directions).into())
     }
 
-    fn part_two(&self, input: &str) -> String {
+    fn part_two(&self, input: &str) -> SolveResult {
         self.part_one(input)
     }
 
-    fn part_three(&self, input: &str) -> String {
+    fn part_three(&self, input: &str) -> SolveResult {
         let directions = [
             (1, 0),
             (-1, 0),
@@ -36,7 +72,7 @@ impl Solution for Q2024_3 {
             (1, -1),
             (-1, -1),
         ];
-        blocks_count(input, &directions).to_string()
+        Ok(blocks_count(input, &directions).into())
     }
 }
 
@@ -85,18 +121,10 @@ fn next_area(area: &HashSet<Position>, directions: &[Direction]) -> HashSet<Posi
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::quests::test::check_examples;
 
     #[test]
-    fn quest2024_3_case() {
-        let input = "..........
-..###.##..
-...####...
-..######..
-..######..
-...####...
-..........";
-        let quest = Q2024_3;
-        assert_eq!(quest.part_one(input), "35");
-        assert_eq!(quest.part_three(input), "29");
+    fn quest2024_03_examples() {
+        check_examples(assemble());
     }
 }
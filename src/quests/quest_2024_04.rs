@@ -1,33 +1,80 @@
+use std::sync::Arc;
+
 use crate::{
     common::strings::TrimmedSplit,
-    quests::{Quest, QuestInputLoader, Solution},
+    quests::{
+        Event, Example, Quest, QuestInfo, QuestInputLoader, SolveError, SolveResult, Typed,
+        TypedSolution,
+    },
+};
+
+const INFO: QuestInfo = QuestInfo {
+    event: Event::Year(2024),
+    number: 4,
+    name: "Royal Smith's Puzzle",
+    tags: &["math", "median"],
+    difficulty: Some(2),
 };
 
 pub fn assemble() -> Quest {
     Quest {
-        title: "Quest 2024 4: Royal Smith's Puzzle".to_string(),
-        input_loader: QuestInputLoader::with_quest_date(2024, 4),
-        solution: Box::new(Q2024_4),
+        info: INFO,
+        input_loader: QuestInputLoader::with_quest_id(INFO.event, INFO.number),
+        solution: Arc::new(Typed(Q2024_4)),
+        examples: EXAMPLES,
+        source: crate::quest_source!(),
     }
 }
 
+const EXAMPLES: &[Example] = &[
+    Example {
+        part: 1,
+        input: "3
+4
+7
+8",
+        expected: "10",
+    },
+    Example {
+        part: 2,
+        input: "3
+4
+7
+8",
+        expected: "10",
+    },
+    Example {
+        part: 3,
+        input: "2
+4
+5
+6
+8",
+        expected: "8",
+    },
+];
+
 type Int = u64;
 
 struct Q2024_4;
 
-impl Solution for Q2024_4 {
-    fn part_one(&self, input: &str) -> String {
-        let values = parse(input);
-        get_min_strikes(&values).to_string()
+impl TypedSolution for Q2024_4 {
+    type Input = Vec<Int>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        parse(input)
     }
 
-    fn part_two(&self, input: &str) -> String {
-        self.part_one(input)
+    fn part_one(&self, values: &Self::Input) -> SolveResult {
+        Ok(get_min_strikes(values).into())
     }
 
-    fn part_three(&self, input: &str) -> String {
-        let values = parse(input);
-        get_min_bidirectional_strikes(&values).to_string()
+    fn part_two(&self, values: &Self::Input) -> SolveResult {
+        self.part_one(values)
+    }
+
+    fn part_three(&self, values: &Self::Input) -> SolveResult {
+        Ok(get_min_bidirectional_strikes(values).into())
     }
 }
 
@@ -45,16 +92,23 @@ fn get_min_bidirectional_strikes(arr: &[Int]) -> Int {
         .unwrap_or_default()
 }
 
-fn parse(input: &str) -> Vec<Int> {
+fn parse(input: &str) -> Result<Vec<Int>, SolveError> {
     input
-        .trimmed_split()
-        .map(|x| x.parse::<Int>().expect("Invalid input"))
-        .collect::<Vec<_>>()
+        .numbered_trimmed_split()
+        .map(|(line, x)| {
+            x.parse::<Int>().map_err(|err| {
+                SolveError::new("invalid nail height")
+                    .at_line(line)
+                    .with_source(err)
+            })
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::quests::test::check_examples;
 
     #[test]
     fn quest2024_4_part1() {
@@ -67,4 +121,9 @@ mod test {
         let result = get_min_bidirectional_strikes(&[2, 4, 5, 6, 8]);
         assert_eq!(result, 8);
     }
+
+    #[test]
+    fn quest2024_04_examples() {
+        check_examples(assemble());
+    }
 }
@@ -1,78 +1,147 @@
 use std::{
     cmp::Ordering,
     collections::{HashMap, HashSet},
+    sync::Arc,
 };
 
 use crate::{
     common::{strings::TrimmedSplit, Vec2},
-    quests::{Quest, QuestInputLoader, Solution},
+    debug, info,
+    quests::{
+        context::{self, check_cancelled},
+        Event, Example, Quest, QuestInfo, QuestInputLoader, SolveError, SolveResult, Typed,
+        TypedSolution,
+    },
+};
+
+const INFO: QuestInfo = QuestInfo {
+    event: Event::Year(2024),
+    number: 5,
+    name: "Pseudo-Random Clap Dance",
+    tags: &["simulation", "cycle detection"],
+    difficulty: Some(3),
 };
 
 pub fn assemble() -> Quest {
     Quest {
-        title: "Quest 2024 5: Pseudo-Random Clap Dance".to_string(),
-        input_loader: QuestInputLoader::with_quest_date(2024, 5),
-        solution: Box::new(Q2024_5),
+        info: INFO,
+        input_loader: QuestInputLoader::with_quest_id(INFO.event, INFO.number),
+        solution: Arc::new(Typed(Q2024_5)),
+        examples: EXAMPLES,
+        source: crate::quest_source!(),
     }
 }
 
+const EXAMPLES: &[Example] = &[
+    Example {
+        part: 1,
+        input: "2 3 4 5
+3 4 5 2
+4 5 2 3
+5 2 3 4",
+        expected: "2323",
+    },
+    Example {
+        part: 2,
+        input: "2 3 4 5
+6 7 8 9",
+        expected: "50877075",
+    },
+    Example {
+        part: 3,
+        input: "2 3 4 5
+6 7 8 9",
+        expected: "6584",
+    },
+];
+
 struct Q2024_5;
 
-impl Solution for Q2024_5 {
-    fn part_one(&self, input: &str) -> String {
-        let mut data = parse(input);
-        make_movements(&mut data, 10)
+impl TypedSolution for Q2024_5 {
+    type Input = Vec2<Int>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        parse(input)
     }
 
-    fn part_two(&self, input: &str) -> String {
-        let mut data = parse(input);
+    // the dance moves the clappers, so every part starts from its own copy of the columns
+    fn part_one(&self, input: &Self::Input) -> SolveResult {
+        let mut data = input.clone();
+        Ok(make_movements(&mut data, 10)?.into())
+    }
+
+    fn part_two(&self, input: &Self::Input) -> SolveResult {
+        let mut data = input.clone();
         let mut map = HashMap::<String, usize>::new();
-        for step in 0.. {
+        // the dance has finitely many states, so it cycles and some shout repeats forever
+        let mut step = 0;
+        loop {
+            if step % CHECK_INTERVAL == 0 {
+                check_cancelled()?;
+                context::progress(step as u64, None);
+            }
             process_movement(&mut data, step);
-            let output = calculate_output(&data);
+            let output = calculate_output(&data)?;
             let entry = map.entry(output).or_default();
             *entry += 1;
             if *entry == 2024 {
-                let output = calculate_output(&data);
-                let val = output.parse::<usize>().expect("Failed parse output value");
-                return (val * (1 + step)).to_string();
+                context::count("rounds", step as u64 + 1);
+                context::count("outputs", map.len() as u64);
+                let output = calculate_output(&data)?;
+                info!("shout {} repeated 2024 times in round {}", output, step + 1);
+                let val = output.parse::<usize>().map_err(|err| {
+                    SolveError::new(format!("failed to parse output value '{}'", output))
+                        .with_source(err)
+                })?;
+                return Ok((val * (1 + step)).into());
             }
+            step += 1;
         }
-        "Not found".to_string()
     }
 
-    fn part_three(&self, input: &str) -> String {
-        let mut data = parse(input);
+    fn part_three(&self, input: &Self::Input) -> SolveResult {
+        let mut data = input.clone();
         let mut set = HashSet::<String>::new();
         let mut result = String::new();
         let mut last_add = 0;
         for step in 0.. {
+            if step % CHECK_INTERVAL == 0 {
+                check_cancelled()?;
+            }
             process_movement(&mut data, step);
-            let output = calculate_output(&data);
+            let output = calculate_output(&data)?;
             if !set.insert(output.clone()) {
                 if step - last_add > set.len() {
+                    context::count("rounds", step as u64 + 1);
+                    context::count("outputs", set.len() as u64);
                     break;
                 }
                 continue;
             }
             last_add = step;
             if matches!(result.cmp(&output), Ordering::Less) {
+                debug!("new highest shout {} in round {}", output, step + 1);
                 result = output;
             }
         }
-        result
+        Ok(result.into())
     }
 }
 
 type Int = usize;
 
-fn make_movements(data: &mut Vec2<Int>, times: usize) -> String {
+/// Rounds between cancellation checks and progress reports, rounds are too cheap for every one
+const CHECK_INTERVAL: usize = 10_000;
+
+fn make_movements(data: &mut Vec2<Int>, times: usize) -> Result<String, SolveError> {
     for step in 0..times {
         process_movement(data, step);
     }
     calculate_output(data)
 }
 
+/// A column gives away one clapper before it gets one back, so columns parsed from
+/// at least two rows are never empty
 fn process_movement(data: &mut Vec2<Int>, step: usize) {
     let col = step % data.len();
     let val = data[col].remove(0);
@@ -85,23 +154,49 @@ fn process_movement(data: &mut Vec2<Int>, step: usize) {
     data[next_col].insert(pos, val);
 }
 
-fn calculate_output(data: &[Vec<Int>]) -> String {
+fn calculate_output(data: &[Vec<Int>]) -> Result<String, SolveError> {
     data.iter()
-        .map(|x| x.first().unwrap())
-        .map(|x| x.to_string())
-        .collect::<String>()
+        .enumerate()
+        .map(|(col, x)| {
+            x.first()
+                .map(|x| x.to_string())
+                .ok_or_else(|| SolveError::new(format!("column {} is empty", col + 1)))
+        })
+        .collect()
 }
 
-fn parse(input: &str) -> Vec2<Int> {
-    let input = input
-        .trimmed_split()
-        .map(|s| {
-            s.split(' ')
-                .map(|x| x.parse::<Int>().unwrap())
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
-    transpose(&input)
+fn parse(input: &str) -> Result<Vec2<Int>, SolveError> {
+    let mut rows = Vec::new();
+    for (line, s) in input.numbered_trimmed_split() {
+        let mut row = Vec::new();
+        let mut column = 1;
+        for x in s.split(' ') {
+            let value = x.parse::<Int>().map_err(|err| {
+                SolveError::new(format!("invalid number '{}'", x))
+                    .at(line, column)
+                    .with_source(err)
+            })?;
+            if value == 0 {
+                return Err(SolveError::new("clapper number must be positive").at(line, column));
+            }
+            row.push(value);
+            column += x.chars().count() + 1;
+        }
+        if rows
+            .first()
+            .is_some_and(|first: &Vec<Int>| first.len() != row.len())
+        {
+            return Err(SolveError::new("rows must have the same length").at_line(line));
+        }
+        rows.push(row);
+    }
+    if rows.is_empty() {
+        return Err(SolveError::new("empty input"));
+    }
+    if rows.len() < 2 {
+        return Err(SolveError::new("at least 2 rows of clappers expected"));
+    }
+    Ok(transpose(&rows))
 }
 
 fn transpose(source: &[Vec<Int>]) -> Vec2<Int> {
@@ -122,21 +217,19 @@ fn transpose(source: &[Vec<Int>]) -> Vec2<Int> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::quests::test::check_examples;
 
     #[test]
-    fn quest2024_05_part1() {
-        let input = "2 3 4 5
-3 4 5 2
-4 5 2 3
-5 2 3 4";
-        let mut data = parse(input);
-        assert_eq!(make_movements(&mut data, 10), "2323");
+    fn quest2024_05_invalid_input() {
+        assert!(parse("2 3 4 5").is_err());
+        assert!(parse("2 3 4 5\n6 7 8").is_err());
+        assert!(parse("2 0\n3 4").is_err());
+        assert!(calculate_output(&[vec![2], vec![]]).is_err());
+        assert_eq!(calculate_output(&[vec![2, 6], vec![3]]).unwrap(), "23");
     }
 
     #[test]
-    fn quest2024_05_part3() {
-        let input = "2 3 4 5
-6 7 8 9";
-        assert_eq!(Q2024_5.part_three(input), "6584");
+    fn quest2024_05_examples() {
+        check_examples(assemble());
     }
 }
@@ -1,53 +1,104 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use crate::{
     common::strings::TrimmedSplit,
-    quests::{Quest, QuestInputLoader, Solution},
+    quests::{
+        Event, Example, Quest, QuestInfo, QuestInputLoader, SolveError, SolveResult, Typed,
+        TypedSolution,
+    },
+};
+
+const INFO: QuestInfo = QuestInfo {
+    event: Event::Year(2024),
+    number: 6,
+    name: "The Tree of Titans",
+    tags: &["graph", "tree"],
+    difficulty: Some(2),
 };
 
 pub fn assemble() -> Quest {
     Quest {
-        title: "Quest 2024 6: The Tree of Titans".to_string(),
-        input_loader: QuestInputLoader::with_quest_date(2024, 6),
-        solution: Box::new(Q2024_6),
+        info: INFO,
+        input_loader: QuestInputLoader::with_quest_id(INFO.event, INFO.number),
+        solution: Arc::new(Typed(Q2024_6)),
+        examples: EXAMPLES,
+        source: crate::quest_source!(),
     }
 }
 
+const EXAMPLE: &str = "RR:A,B,C
+A:D,E
+B:F,@
+C:G,H
+D:@
+E:@
+F:@
+G:@
+H:@";
+
+const EXAMPLES: &[Example] = &[
+    Example {
+        part: 1,
+        input: EXAMPLE,
+        expected: "RRB@",
+    },
+    Example {
+        part: 2,
+        input: EXAMPLE,
+        expected: "RB@",
+    },
+];
+
 struct Q2024_6;
 
-impl Solution for Q2024_6 {
-    fn part_one(&self, input: &str) -> String {
-        let tree = parse(input);
-        find_unique_path(&tree).expect("Path not found").join("")
+impl TypedSolution for Q2024_6 {
+    type Input = Tree;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        parse(input)
+    }
+
+    fn part_one(&self, tree: &Self::Input) -> SolveResult {
+        let path = find_unique_path(tree).ok_or_else(path_not_found)?;
+        Ok(path.join("").into())
     }
 
-    fn part_two(&self, input: &str) -> String {
-        let tree = parse(input);
-        find_unique_path(&tree)
-            .expect("Path not found")
+    fn part_two(&self, tree: &Self::Input) -> SolveResult {
+        let path = find_unique_path(tree).ok_or_else(path_not_found)?;
+        Ok(path
             .iter()
             .filter_map(|s| s.chars().next())
             .collect::<String>()
+            .into())
     }
 
-    fn part_three(&self, input: &str) -> String {
-        self.part_two(input)
+    fn part_three(&self, tree: &Self::Input) -> SolveResult {
+        self.part_two(tree)
     }
 }
 
 type Node = String;
 type Tree = HashMap<Node, HashSet<Node>>;
 
-fn parse(input: &str) -> Tree {
+fn path_not_found() -> SolveError {
+    SolveError::new("unique path not found")
+}
+
+fn parse(input: &str) -> Result<Tree, SolveError> {
     let mut tree = Tree::new();
-    for line in input.trimmed_split() {
-        let (node, connections) = line.split_once(':').expect("Invalid input format");
+    for (number, line) in input.numbered_trimmed_split() {
+        let Some((node, connections)) = line.split_once(':') else {
+            return Err(SolveError::new("invalid format, expected 'NODE:A,B,...'").at_line(number));
+        };
         let set = tree.entry(node.to_string()).or_default();
         for connection in connections.split(',') {
             set.insert(connection.to_string());
         }
     }
-    tree
+    Ok(tree)
 }
 
 fn find_unique_path(tree: &Tree) -> Option<Vec<String>> {
@@ -86,3 +137,14 @@ fn find_unique_path(tree: &Tree) -> Option<Vec<String>> {
         .find(|(_, val)| val.len() == 1)
         .and_then(|(_, set)| set.iter().next().cloned())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::quests::test::check_examples;
+
+    #[test]
+    fn quest2024_06_examples() {
+        check_examples(assemble());
+    }
+}
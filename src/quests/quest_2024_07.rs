@@ -1,27 +1,62 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use crate::{
     common::{strings::TrimmedSplit, Direction},
-    quests::{Quest, QuestInputLoader, Solution},
+    quests::{
+        context::{self, check_cancelled},
+        Event, Example, Quest, QuestInfo, QuestInputLoader, SolveError, SolveResult, Typed,
+        TypedSolution,
+    },
+    warn,
+};
+
+const INFO: QuestInfo = QuestInfo {
+    event: Event::Year(2024),
+    number: 7,
+    name: "Not Fast but Furious",
+    tags: &["simulation", "grid", "combinatorics"],
+    difficulty: Some(3),
 };
 
 pub fn assemble() -> Quest {
     Quest {
-        title: "Quest 2024 7: Not Fast but Furious".to_string(),
-        input_loader: QuestInputLoader::with_quest_date(2024, 7),
-        solution: Box::new(Q2024_7),
+        info: INFO,
+        input_loader: QuestInputLoader::with_quest_id(INFO.event, INFO.number),
+        solution: Arc::new(Typed(Q2024_7)),
+        examples: EXAMPLES,
+        source: crate::quest_source!(),
     }
 }
 
+// examples of the other parts use a different racetrack
+const EXAMPLES: &[Example] = &[Example {
+    part: 1,
+    input: "A:+,-,=,=
+B:+,=,-,+
+C:=,-,+,+
+D:=,=,=,+",
+    expected: "BDCA",
+}];
+
 struct Q2024_7;
 
-impl Solution for Q2024_7 {
-    fn part_one(&self, input: &str) -> String {
-        let data = parse(input);
-        perform(data, 10)
+type Plans = HashMap<String, Vec<Action>>;
+
+impl TypedSolution for Q2024_7 {
+    type Input = Plans;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+        parse(input)
+    }
+
+    fn part_one(&self, plans: &Self::Input) -> SolveResult {
+        Ok(perform(plans, 10).into())
     }
 
-    fn part_two(&self, input: &str) -> String {
+    fn part_two(&self, plans: &Self::Input) -> SolveResult {
         let racetrack = parse_racetrack(
             "S-=++=-==++=++=-=+=-=+=+=--=-=++=-==++=-+=-=+=-=+=+=++=-+==++=++=-=-=--
 -                                                                     -
@@ -33,11 +68,10 @@ impl Solution for Q2024_7 {
 -                                                                     -
 --==++++==+=+++-=+=-=+=-+-=+-=+-=+=-=+=--=+++=++=+++==++==--=+=++==+++-",
         );
-        let data = parse(input);
-        perform_with_racetrack(data, 10, &racetrack)
+        Ok(perform_with_racetrack(plans, 10, &racetrack).into())
     }
 
-    fn part_three(&self, input: &str) -> String {
+    fn part_three(&self, plans: &Self::Input) -> SolveResult {
         let racetrack = parse_racetrack(
             r#"S+= +=-== +=++=     =+=+=--=    =-= ++=     +=-  =+=++=-+==+ =++=-=-=--
 - + +   + =   =     =      =   == = - -     - =  =         =-=        -
@@ -50,26 +84,44 @@ impl Solution for Q2024_7 {
 -               = + + =   +  -  = + = = +   =        +     =          -
 --==++++==+=+++-= =-= =-+-=  =+-= =-= =--   +=++=+++==     -=+=++==+++-"#,
         );
-        let actions = parse(input).values().next().cloned().expect("Empty input");
-        let base = ranking_with_racetrack(&actions, &racetrack);
-        total_winning_plans(&racetrack, base).to_string()
+        let actions = plans
+            .values()
+            .next()
+            .ok_or_else(|| SolveError::new("empty input"))?;
+        if plans.len() > 1 {
+            warn!("{} plans found, only one is used", plans.len());
+        }
+        let base = ranking_with_racetrack(actions, &racetrack);
+        Ok(total_winning_plans(&racetrack, base)?.into())
     }
 }
 
-fn total_winning_plans(racetrack: &[Action], base: usize) -> usize {
+/// Plans with 5 increments, 3 decrements and 3 keeps: 11! / (5! * 3! * 3!)
+const PLAN_COUNT: u64 = 9240;
+
+struct PlanSearch<'a> {
+    racetrack: &'a [Action],
+    base: usize,
+    acc: Vec<Action>,
+    winning: HashSet<String>,
+    evaluated: u64,
+}
+
+fn total_winning_plans(racetrack: &[Action], base: usize) -> Result<usize, SolveError> {
     fn dfs(
-        racetrack: &[Action],
-        base: usize,
+        search: &mut PlanSearch,
         pluses: usize,
         minuses: usize,
         equals: usize,
-        acc: &mut Vec<Action>,
-        output: &mut HashSet<String>,
-    ) {
+    ) -> Result<(), SolveError> {
         if pluses == 0 && minuses == 0 && equals == 0 {
-            let score = ranking_with_racetrack(acc, racetrack);
-            if score > base {
-                let val = acc
+            check_cancelled()?;
+            search.evaluated += 1;
+            context::progress(search.evaluated, Some(PLAN_COUNT));
+            let score = ranking_with_racetrack(&search.acc, search.racetrack);
+            if score > search.base {
+                let val = search
+                    .acc
                     .iter()
                     .map(|x| match x {
                         Action::Dec => '-',
@@ -77,35 +129,44 @@ fn total_winning_plans(racetrack: &[Action], base: usize) -> usize {
                         _ => '=',
                     })
                     .collect::<String>();
-                output.insert(val);
+                search.winning.insert(val);
             }
-            return;
+            return Ok(());
         }
         if pluses > 0 {
-            acc.push(Action::Inc);
-            dfs(racetrack, base, pluses - 1, minuses, equals, acc, output);
-            acc.pop();
+            search.acc.push(Action::Inc);
+            dfs(search, pluses - 1, minuses, equals)?;
+            search.acc.pop();
         }
 
         if minuses > 0 {
-            acc.push(Action::Dec);
-            dfs(racetrack, base, pluses, minuses - 1, equals, acc, output);
-            acc.pop();
+            search.acc.push(Action::Dec);
+            dfs(search, pluses, minuses - 1, equals)?;
+            search.acc.pop();
         }
 
         if equals > 0 {
-            acc.push(Action::Keep);
-            dfs(racetrack, base, pluses, minuses, equals - 1, acc, output);
-            acc.pop();
+            search.acc.push(Action::Keep);
+            dfs(search, pluses, minuses, equals - 1)?;
+            search.acc.pop();
         }
+        Ok(())
     }
 
-    let mut output = HashSet::new();
-    dfs(racetrack, base, 5, 3, 3, &mut Vec::new(), &mut output);
-    output.len()
+    let mut search = PlanSearch {
+        racetrack,
+        base,
+        acc: Vec::new(),
+        winning: HashSet::new(),
+        evaluated: 0,
+    };
+    dfs(&mut search, 5, 3, 3)?;
+    context::count("plans", search.evaluated);
+    context::count("winning plans", search.winning.len() as u64);
+    Ok(search.winning.len())
 }
 
-fn perform(data: HashMap<String, Vec<Action>>, segments: usize) -> String {
+fn perform(data: &Plans, segments: usize) -> String {
     let mut scores = HashMap::<String, Vec<usize>>::new();
     for segment in 0..segments {
         for key in data.keys() {
@@ -120,11 +181,7 @@ fn perform(data: HashMap<String, Vec<Action>>, segments: usize) -> String {
     calc_ranking(&scores)
 }
 
-fn perform_with_racetrack(
-    data: HashMap<String, Vec<Action>>,
-    rounds: usize,
-    racetrack: &[Action],
-) -> String {
+fn perform_with_racetrack(data: &Plans, rounds: usize, racetrack: &[Action]) -> String {
     let mut scores = HashMap::<String, Vec<usize>>::new();
     for round in 0..rounds * racetrack.len() {
         for key in data.keys() {
@@ -175,7 +232,7 @@ fn calc_ranking(scores: &HashMap<String, Vec<usize>>) -> String {
             (key, sum)
         })
         .collect::<Vec<_>>();
-    scores.sort_by(|a, b| b.1.cmp(&a.1));
+    scores.sort_by_key(|b| std::cmp::Reverse(b.1));
     scores
         .iter()
         .map(|(val, _)| *val)
@@ -191,20 +248,28 @@ enum Action {
     Keep,
 }
 
-fn parse(input: &str) -> HashMap<String, Vec<Action>> {
+fn parse(input: &str) -> Result<Plans, SolveError> {
     input
-        .trimmed_split()
-        .map(parse_line)
-        .collect::<HashMap<_, _>>()
+        .numbered_trimmed_split()
+        .map(|(number, line)| parse_line(line).map_err(|err| err.at_line(number)))
+        .collect()
 }
 
-fn parse_line(input: &str) -> (String, Vec<Action>) {
-    let (name, actions) = input.split_once(':').expect("Invalid input format");
+fn parse_line(input: &str) -> Result<(String, Vec<Action>), SolveError> {
+    let Some((name, actions)) = input.split_once(':') else {
+        return Err(SolveError::new("invalid format, expected 'NAME:<actions>'"));
+    };
     let actions = actions
         .split(',')
-        .map(|action| parse_action(action.chars().next().expect("Invalid actions format")))
-        .collect::<Vec<_>>();
-    (name.to_string(), actions)
+        .map(|action| {
+            action
+                .chars()
+                .next()
+                .map(parse_action)
+                .ok_or_else(|| SolveError::new("empty action"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((name.to_string(), actions))
 }
 
 fn parse_action(ch: char) -> Action {
@@ -254,15 +319,8 @@ fn parse_racetrack(input: &str) -> Vec<Action> {
 #[cfg(test)]
 mod test {
     use super::*;
-
-    #[test]
-    fn quest2024_07_part1() {
-        let input = "A:+,-,=,=
-B:+,=,-,+
-C:=,-,+,+
-D:=,=,=,+";
-        assert_eq!("BDCA", Q2024_7.part_one(input))
-    }
+    use crate::debug;
+    use crate::quests::test::check_examples;
 
     #[test]
     fn quest2024_07_parse_track() {
@@ -270,9 +328,9 @@ D:=,=,=,+";
 -   +
 =+=-+";
         let track = parse_racetrack(input);
-        let (_, line) = parse_line("X:+,=,=,=,+,+,-,=,+,=,-,S");
-        println!("PARSED: {track:?}");
-        println!("INPUT:  {line:?}");
+        let (_, line) = parse_line("X:+,=,=,=,+,+,-,=,+,=,-,S").unwrap();
+        debug!("parsed: {track:?}");
+        debug!("input:  {line:?}");
         assert_eq!(track, line)
     }
 
@@ -287,8 +345,13 @@ D:=,=,=,+";
 B:+,=,-,+
 C:=,-,+,+
 D:=,=,=,+";
-        let data = parse(input);
-        let ranking = perform_with_racetrack(data, 10, &racetrack);
+        let data = parse(input).unwrap();
+        let ranking = perform_with_racetrack(&data, 10, &racetrack);
         assert_eq!(ranking, "DCBA")
     }
+
+    #[test]
+    fn quest2024_07_examples() {
+        check_examples(assemble());
+    }
 }
@@ -0,0 +1,100 @@
+use super::{
+    downcast, Answer, ParsedInput, Part, Solution, SolveError, SolveResult, DEFAULT_PARTS,
+};
+use std::sync::Arc;
+
+/// Solution with a separate parse phase, the parsed input is reused by parts sharing it
+pub trait TypedSolution: Send + Sync {
+    type Input: Send + Sync + 'static;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, SolveError>;
+
+    fn parts(&self) -> &'static [Part] {
+        DEFAULT_PARTS
+    }
+
+    fn part_one(&self, _input: &Self::Input) -> SolveResult {
+        Ok(Answer::NotImplemented)
+    }
+
+    fn part_two(&self, _input: &Self::Input) -> SolveResult {
+        Ok(Answer::NotImplemented)
+    }
+
+    fn part_three(&self, _input: &Self::Input) -> SolveResult {
+        Ok(Answer::NotImplemented)
+    }
+
+    /// Solves part by its zero-based index
+    fn solve(&self, part: usize, input: &Self::Input) -> SolveResult {
+        match part {
+            0 => self.part_one(input),
+            1 => self.part_two(input),
+            2 => self.part_three(input),
+            _ => Err(SolveError::new(format!("part {} not found", part + 1))),
+        }
+    }
+}
+
+/// Adapts a typed solution to `Solution`
+pub struct Typed<S>(pub S);
+
+impl<S: TypedSolution> Solution for Typed<S> {
+    fn parts(&self) -> &'static [Part] {
+        self.0.parts()
+    }
+
+    fn solve(&self, part: usize, input: &str) -> SolveResult {
+        let input = self.0.parse(input)?;
+        self.0.solve(part, &input)
+    }
+
+    fn parse(&self, input: &str) -> Result<ParsedInput, SolveError> {
+        Ok(Arc::new(self.0.parse(input)?))
+    }
+
+    fn solve_parsed(&self, part: usize, input: &ParsedInput) -> SolveResult {
+        self.0.solve(part, downcast::<S::Input>(input)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Sum;
+
+    impl TypedSolution for Sum {
+        type Input = Vec<i64>;
+
+        fn parse(&self, input: &str) -> Result<Self::Input, SolveError> {
+            input
+                .split(',')
+                .map(|s| {
+                    s.parse::<i64>()
+                        .map_err(|err| SolveError::new("nan").with_source(err))
+                })
+                .collect()
+        }
+
+        fn part_one(&self, input: &Self::Input) -> SolveResult {
+            Ok(input.iter().sum::<i64>().into())
+        }
+    }
+
+    #[test]
+    fn typed_solution() {
+        let solution = Typed(Sum);
+        assert_eq!(solution.solve(0, "1,2,3").unwrap(), Answer::Int(6));
+        assert!(solution.solve(0, "1,x").is_err());
+        let parsed = solution.parse("4,5").unwrap();
+        assert_eq!(solution.solve_parsed(0, &parsed).unwrap(), Answer::Int(9));
+        assert_eq!(
+            solution.solve_parsed(1, &parsed).unwrap(),
+            Answer::NotImplemented
+        );
+        // text input of another solution is rejected
+        let text: ParsedInput = Arc::new("4,5".to_string());
+        assert!(solution.solve_parsed(0, &text).is_err());
+    }
+}
@@ -0,0 +1,241 @@
+use crate::quests::{Event, InputOptions, Quest, QuestRegistry};
+use crate::runner::{self, RunConfig};
+use std::io::{self, BufRead, Write};
+use std::time::Instant;
+
+/// Line that ends a pasted input
+const TERMINATOR: &str = "EOF";
+
+const HELP: &str = "Commands:
+  list [TAG]             print registered quests, optionally with the tag
+  event [EVENT]          show or switch the current event (2024, s1)
+  run QUEST [PART]       solve a quest of the current event with its input files
+  paste QUEST PART       read an input until a line 'EOF' and solve the part for it
+  time                   toggle printing the elapsed time of run and paste
+  help                   show this screen
+  quit                   leave the shell";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ReplCommand {
+    List(Option<String>),
+    Event(Option<Event>),
+    Run { quest: usize, part: Option<usize> },
+    Paste { quest: usize, part: usize },
+    Time,
+    Help,
+    Quit,
+}
+
+/// Interactive shell solving quests of the registry, reads commands from stdin until `quit`
+pub fn repl(registry: &QuestRegistry, config: &RunConfig, input: &InputOptions) {
+    let mut shell = Shell {
+        registry,
+        config,
+        input,
+        event: registry.latest().map(|quest| quest.info.event),
+        timing: false,
+    };
+    println!("Type 'help' to see commands, 'quit' to leave");
+    let mut stdin = io::stdin().lock();
+    loop {
+        if let Some(event) = shell.event {
+            print!("{}> ", event);
+        } else {
+            print!("> ");
+        }
+        io::stdout().flush().ok();
+        let mut line = String::new();
+        match stdin.read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(err) => {
+                eprintln!("[Error] failed to read command: {}", err);
+                break;
+            }
+        }
+        let command = match parse_command(&line) {
+            Ok(Some(command)) => command,
+            Ok(None) => continue,
+            Err(err) => {
+                println!("[Error] {}", err);
+                continue;
+            }
+        };
+        if command == ReplCommand::Quit {
+            break;
+        }
+        let solves = matches!(command, ReplCommand::Run { .. } | ReplCommand::Paste { .. });
+        let now = Instant::now();
+        if let Err(err) = shell.execute(command, &mut stdin) {
+            println!("[Error] {}", err);
+        }
+        if shell.timing && solves {
+            println!("Time: {}", runner::format_duration(now.elapsed()));
+        }
+    }
+}
+
+struct Shell<'a> {
+    registry: &'a QuestRegistry,
+    config: &'a RunConfig,
+    input: &'a InputOptions,
+    event: Option<Event>,
+    timing: bool,
+}
+
+impl Shell<'_> {
+    fn execute<R: BufRead>(&mut self, command: ReplCommand, reader: &mut R) -> Result<(), String> {
+        match command {
+            ReplCommand::List(tag) => crate::list(self.registry, tag.as_deref()),
+            ReplCommand::Event(None) => match self.event {
+                Some(event) => println!("Current event: {}", event),
+                None => println!("No quests registered"),
+            },
+            ReplCommand::Event(Some(event)) => {
+                if !self.registry.events().iter().any(|(e, _)| *e == event) {
+                    return Err(format!("event {} has no registered quests", event));
+                }
+                self.event = Some(event);
+            }
+            ReplCommand::Run { quest, part } => {
+                let quest = self.quest(quest)?;
+                runner::execute_parts(
+                    crate::label(&quest.info),
+                    &quest,
+                    self.config,
+                    part.as_slice(),
+                    None,
+                );
+            }
+            ReplCommand::Paste { quest, part } => {
+                let quest = self.quest(quest)?;
+                if !quest.solution.parts().iter().any(|p| p.number == part) {
+                    return Err(format!("part {} is not declared by the quest", part));
+                }
+                println!("Paste the input, finish with a line '{}'", TERMINATOR);
+                let input =
+                    read_input(reader).map_err(|err| format!("failed to read input: {}", err))?;
+                if input.is_empty() {
+                    return Err("empty input".to_string());
+                }
+                runner::solve_text(&quest, part, input, self.config);
+            }
+            ReplCommand::Time => {
+                self.timing = !self.timing;
+                println!("Timing is {}", if self.timing { "on" } else { "off" });
+            }
+            ReplCommand::Help => println!("{}", HELP),
+            ReplCommand::Quit => {}
+        }
+        Ok(())
+    }
+
+    /// Quest of the current event with the configured input files
+    fn quest(&self, number: usize) -> Result<Quest, String> {
+        let event = self.event.ok_or("no quests registered")?;
+        let mut quest = self
+            .registry
+            .quest(event, number)
+            .ok_or_else(|| format!("quest {} {} not found", event, number))?;
+        quest.input_loader.configure(self.input);
+        Ok(quest)
+    }
+}
+
+/// Parses a command line, `None` for an empty line
+fn parse_command(line: &str) -> Result<Option<ReplCommand>, String> {
+    let args = line.split_whitespace().collect::<Vec<_>>();
+    let Some((command, rest)) = args.split_first() else {
+        return Ok(None);
+    };
+    let number = |name: &str, value: &str| {
+        value
+            .parse::<usize>()
+            .ok()
+            .filter(|n| *n > 0)
+            .ok_or_else(|| format!("invalid {} number '{}'", name, value))
+    };
+    let command = match (*command, rest) {
+        ("list", []) => ReplCommand::List(None),
+        ("list", [tag]) => ReplCommand::List(Some(tag.to_string())),
+        ("event", []) => ReplCommand::Event(None),
+        ("event", [event]) => ReplCommand::Event(Some(event.parse()?)),
+        ("run", [quest]) => ReplCommand::Run {
+            quest: number("quest", quest)?,
+            part: None,
+        },
+        ("run", [quest, part]) => ReplCommand::Run {
+            quest: number("quest", quest)?,
+            part: Some(number("part", part)?),
+        },
+        ("paste", [quest, part]) => ReplCommand::Paste {
+            quest: number("quest", quest)?,
+            part: number("part", part)?,
+        },
+        ("time", []) => ReplCommand::Time,
+        ("help", []) => ReplCommand::Help,
+        ("quit" | "exit", []) => ReplCommand::Quit,
+        ("list" | "event" | "run" | "paste" | "time" | "help" | "quit" | "exit", _) => {
+            return Err(format!("wrong arguments of '{}', see 'help'", command))
+        }
+        _ => return Err(format!("unknown command '{}', see 'help'", command)),
+    };
+    Ok(Some(command))
+}
+
+/// Reads lines until the terminator or the end of the stream, line breaks are kept
+/// except after the last line
+fn read_input<R: BufRead>(reader: &mut R) -> io::Result<String> {
+    let mut lines = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim_end() == TERMINATOR {
+            break;
+        }
+        lines.push(line);
+    }
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn repl_parse_command() {
+        assert_eq!(parse_command("  \n"), Ok(None));
+        assert_eq!(
+            parse_command("run 7 2\n"),
+            Ok(Some(ReplCommand::Run {
+                quest: 7,
+                part: Some(2)
+            }))
+        );
+        assert_eq!(
+            parse_command("paste 7 1"),
+            Ok(Some(ReplCommand::Paste { quest: 7, part: 1 }))
+        );
+        assert_eq!(
+            parse_command("event s1"),
+            Ok(Some(ReplCommand::Event(Some(Event::Story(1)))))
+        );
+        assert_eq!(parse_command("exit"), Ok(Some(ReplCommand::Quit)));
+        assert!(parse_command("run 0").is_err());
+        assert!(parse_command("paste 7").is_err());
+        assert!(parse_command("solve 7").is_err());
+    }
+
+    #[test]
+    fn repl_read_input() {
+        let mut reader = Cursor::new("A:+,-\nB:=,+\nEOF\nrun 7\n");
+        assert_eq!(read_input(&mut reader).unwrap(), "A:+,-\nB:=,+");
+        // the rest of the stream is left for the next commands
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line, "run 7\n");
+        // the end of the stream ends the input too
+        let mut reader = Cursor::new("S+=\n-+-");
+        assert_eq!(read_input(&mut reader).unwrap(), "S+=\n-+-");
+    }
+}
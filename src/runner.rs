@@ -0,0 +1,1081 @@
+use crate::cache::{self, AnswerCache, Entry};
+use crate::export::Format;
+use crate::isolation::PanicReport;
+use crate::pool;
+use crate::quests::context::{Diagnostics, SolveContext};
+use crate::quests::{Answer, Event, ParsedInput, Part, Quest, QuestError, SolveError};
+use crate::worker::{self, PartRun};
+use std::io::IsTerminal;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunConfig {
+    pub part: Option<usize>,
+    pub timeout: Option<Duration>,
+    pub examples: bool,
+    /// Number of parallel jobs, 1 solves everything serially
+    pub jobs: usize,
+    pub format: Format,
+    /// Serve and store answers in the answer cache, `None` enables it if `KOA_CACHE` is set
+    pub cache: Option<bool>,
+}
+
+impl RunConfig {
+    pub fn use_cache(&self) -> bool {
+        self.cache.unwrap_or_else(|| {
+            std::env::var("KOA_CACHE").is_ok_and(|value| !value.is_empty() && value != "0")
+        })
+    }
+}
+
+impl Default for RunConfig {
+    fn default() -> Self {
+        Self {
+            part: None,
+            timeout: None,
+            examples: false,
+            jobs: 1,
+            format: Format::Text,
+            cache: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mismatch {
+    Value,
+    Type { actual: &'static str },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        mismatch: Mismatch,
+    },
+    Unknown,
+}
+
+impl Verdict {
+    pub fn check(answer: &Answer, expected: Option<&str>) -> Self {
+        let Some(expected) = expected else {
+            return Verdict::Unknown;
+        };
+        // exact textual match is accepted for any answer type, e.g. digit sequences
+        if answer.to_string() == expected {
+            return Verdict::Pass;
+        }
+        let expected_number = expected.parse::<i128>().ok();
+        let mismatch = match (answer.as_integer(), expected_number) {
+            (Some(actual), Some(expected)) if actual == expected => return Verdict::Pass,
+            (Some(_), Some(_)) => Mismatch::Value,
+            (None, Some(_)) if answer.to_string().parse::<i128>().is_ok() => Mismatch::Value,
+            (None, None) if matches!(answer, Answer::Text(_)) => Mismatch::Value,
+            _ => Mismatch::Type {
+                actual: answer.kind(),
+            },
+        };
+        Verdict::Fail {
+            expected: expected.to_string(),
+            mismatch,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Verdict::Pass => "PASS",
+            Verdict::Fail { .. } => "FAIL",
+            Verdict::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Fail {
+                expected,
+                mismatch: Mismatch::Value,
+            } => write!(f, "FAIL, expected {}", expected),
+            Verdict::Fail {
+                expected,
+                mismatch: Mismatch::Type { actual },
+            } => write!(f, "FAIL, expected {}, got {} answer", expected, actual),
+            _ => write!(f, "{}", self.label()),
+        }
+    }
+}
+
+pub enum Outcome {
+    Solved(Answer),
+    Failed(SolveError),
+    Panicked(PanicReport),
+    Timeout,
+    NoInput(QuestError),
+}
+
+pub struct PartReport {
+    pub part: usize,
+    pub outcome: Outcome,
+    pub verdict: Verdict,
+    /// Parse time, `None` if the part reused input parsed for another part
+    pub parse: Option<Duration>,
+    /// Solve time
+    pub duration: Duration,
+    /// The answer is served from the answer cache
+    pub cached: bool,
+    /// Answer cache key of the part input
+    pub cache_key: Option<u64>,
+    /// Messages and counters reported by the solution while parsing and solving
+    pub diagnostics: Diagnostics,
+}
+
+impl PartReport {
+    pub fn total(&self) -> Duration {
+        self.parse.unwrap_or_default() + self.duration
+    }
+
+    /// Verdict of the solved part or the kind of its failure
+    pub fn status(&self) -> &'static str {
+        match self.outcome {
+            Outcome::Failed(_) | Outcome::Panicked(_) => "ERROR",
+            Outcome::Timeout => "TIMEOUT",
+            Outcome::NoInput(_) => "NO_INPUT",
+            _ => self.verdict.label(),
+        }
+    }
+
+    /// A part without input was requested but not solved, so it fails the run as well
+    fn is_failure(&self) -> bool {
+        matches!(
+            self.outcome,
+            Outcome::Failed(_) | Outcome::Panicked(_) | Outcome::Timeout | Outcome::NoInput(_)
+        ) || matches!(self.verdict, Verdict::Fail { .. })
+    }
+
+    /// Answer or short description of the failure
+    pub fn summary(&self) -> String {
+        match &self.outcome {
+            Outcome::Solved(answer) => answer.to_string(),
+            Outcome::Failed(err) => format!("error: {}", err),
+            Outcome::Panicked(report) => report.to_string(),
+            Outcome::Timeout => "timeout".to_string(),
+            Outcome::NoInput(QuestError::Io { .. }) => "input error".to_string(),
+            Outcome::NoInput(_) => "no input".to_string(),
+        }
+    }
+}
+
+pub struct QuestReport {
+    pub event: Event,
+    pub quest: usize,
+    pub label: String,
+    pub title: String,
+    pub parts: Vec<PartReport>,
+    pub examples: Vec<PartReport>,
+}
+
+impl QuestReport {
+    pub fn total(&self) -> Duration {
+        self.parts.iter().map(|p| p.total()).sum()
+    }
+
+    fn parse_total(&self) -> Duration {
+        self.parts.iter().filter_map(|p| p.parse).sum()
+    }
+
+    pub fn has_failures(&self) -> bool {
+        self.parts
+            .iter()
+            .chain(self.examples.iter())
+            .any(|p| p.is_failure())
+    }
+
+    fn part(&self, part: usize) -> Option<&PartReport> {
+        self.parts.iter().find(|p| p.part == part)
+    }
+
+    fn status(&self) -> String {
+        let count = |label: &str| self.parts.iter().filter(|p| p.status() == label).count();
+        let mut status = ["PASS", "FAIL", "ERROR", "TIMEOUT", "NO_INPUT", "UNKNOWN"]
+            .iter()
+            .map(|label| (label, count(label)))
+            .filter(|(_, count)| *count > 0)
+            .map(|(label, count)| format!("{} {}", count, label))
+            .collect::<Vec<_>>();
+        let cached = self.parts.iter().filter(|p| p.cached).count();
+        if cached > 0 {
+            status.push(format!("{} cached", cached));
+        }
+        if !self.examples.is_empty() {
+            let passed = self.examples.iter().filter(|p| !p.is_failure()).count();
+            status.push(format!("examples {}/{}", passed, self.examples.len()));
+        }
+        status.join(", ")
+    }
+}
+
+/// Destination of the progress messages, buffered while quests run in parallel
+pub enum Output {
+    Stdout,
+    Stderr,
+    Buffer(Vec<String>),
+}
+
+impl Output {
+    /// Progress goes to stderr if stdout is taken by machine-readable records
+    fn console(format: Format) -> Self {
+        match format {
+            Format::Text => Output::Stdout,
+            Format::Json | Format::Csv => Output::Stderr,
+        }
+    }
+
+    fn line(&mut self, line: String) {
+        match self {
+            Output::Stdout => println!("{}", line),
+            Output::Stderr => eprintln!("{}", line),
+            Output::Buffer(lines) => lines.push(line),
+        }
+    }
+
+    /// Progress is rewritten in place, so it's shown only on a terminal and not for buffered output
+    fn shows_progress(&self) -> bool {
+        !matches!(self, Output::Buffer(_)) && std::io::stderr().is_terminal()
+    }
+
+    fn into_lines(self) -> Vec<String> {
+        match self {
+            Output::Stdout | Output::Stderr => Vec::new(),
+            Output::Buffer(lines) => lines,
+        }
+    }
+}
+
+/// Solves the quest parts one after another
+fn execute(
+    label: String,
+    quest: &Quest,
+    config: &RunConfig,
+    cache: Option<&AnswerCache>,
+) -> QuestReport {
+    execute_parts(label, quest, config, config.part.as_slice(), cache)
+}
+
+/// Solves the selected parts one after another, all declared parts if the selection is empty
+pub fn execute_parts(
+    label: String,
+    quest: &Quest,
+    config: &RunConfig,
+    selected: &[usize],
+    cache: Option<&AnswerCache>,
+) -> QuestReport {
+    let mut out = Output::console(config.format);
+    let (mut report, groups) = plan(label, quest, selected, &mut out);
+    if config.examples {
+        report.examples = check_examples(quest, config, &mut out);
+    }
+    let answers = quest.input_loader.load_answers();
+    for group in groups {
+        let parts = solve_group(quest, &group, &answers, config, cache, &mut out);
+        report.parts.extend(parts);
+    }
+    report
+}
+
+/// Unit of parallel work, the index refers to the quest
+enum Job {
+    Examples(usize),
+    Parts(usize, Vec<Part>),
+}
+
+/// Solves the quests, in parallel if `config.jobs` allows it. Solved answers are stored
+/// in the answer cache if it's enabled
+pub fn execute_all(quests: &[(String, Quest)], config: &RunConfig) -> Vec<QuestReport> {
+    let mut cache = config
+        .use_cache()
+        .then(|| AnswerCache::load(cache::cache_path()));
+    let reports = if config.jobs <= 1 {
+        quests
+            .iter()
+            .map(|(label, quest)| execute(label.clone(), quest, config, cache.as_ref()))
+            .collect()
+    } else {
+        execute_parallel(quests, config, cache.as_ref())
+    };
+    if let Some(cache) = &mut cache {
+        update_cache(cache, &reports);
+        if let Err(err) = cache.save(cache::cache_path()) {
+            eprintln!("[Warn] failed to write the answer cache: {}", err);
+        }
+    }
+    reports
+}
+
+/// Stores solved answers which are not known to be wrong
+fn update_cache(cache: &mut AnswerCache, reports: &[QuestReport]) {
+    for report in reports {
+        for part in &report.parts {
+            let (Outcome::Solved(answer), Some(key), false) =
+                (&part.outcome, part.cache_key, part.cached)
+            else {
+                continue;
+            };
+            if matches!(answer, Answer::NotImplemented)
+                || matches!(part.verdict, Verdict::Fail { .. })
+            {
+                continue;
+            }
+            let entry = Entry {
+                key,
+                answer: answer.to_string(),
+                duration: part.total(),
+            };
+            cache.insert((report.event, report.quest, part.part), entry);
+        }
+    }
+}
+
+/// Solves the quests on `config.jobs` threads. Examples and every group of parts sharing
+/// an input are independent jobs, the output of each quest is printed in order once it's solved
+fn execute_parallel(
+    quests: &[(String, Quest)],
+    config: &RunConfig,
+    cache: Option<&AnswerCache>,
+) -> Vec<QuestReport> {
+    let mut pending = Vec::new();
+    let mut jobs = Vec::new();
+    for (i, (label, quest)) in quests.iter().enumerate() {
+        let mut out = Output::Buffer(Vec::new());
+        let (report, groups) = plan(label.clone(), quest, config.part.as_slice(), &mut out);
+        let count = jobs.len();
+        if config.examples {
+            jobs.push(Job::Examples(i));
+        }
+        jobs.extend(groups.into_iter().map(|group| Job::Parts(i, group)));
+        pending.push((report, out.into_lines(), jobs.len() - count));
+    }
+    let answers = quests
+        .iter()
+        .map(|(_, quest)| quest.input_loader.load_answers())
+        .collect::<Vec<_>>();
+    let owners = jobs
+        .iter()
+        .map(|job| match job {
+            Job::Examples(i) => (*i, true),
+            Job::Parts(i, _) => (*i, false),
+        })
+        .collect::<Vec<_>>();
+    let mut console = Output::console(config.format);
+    let mut printed = 0;
+    let mut flush = |pending: &[(QuestReport, Vec<String>, usize)]| {
+        while printed < pending.len() && pending[printed].2 == 0 {
+            for line in &pending[printed].1 {
+                console.line(line.clone());
+            }
+            printed += 1;
+        }
+    };
+    flush(&pending);
+    pool::run_ordered(
+        config.jobs,
+        jobs,
+        |job| {
+            let mut out = Output::Buffer(Vec::new());
+            let reports = match job {
+                Job::Examples(i) => check_examples(&quests[i].1, config, &mut out),
+                Job::Parts(i, group) => {
+                    solve_group(&quests[i].1, &group, &answers[i], config, cache, &mut out)
+                }
+            };
+            (reports, out.into_lines())
+        },
+        |index, (reports, lines)| {
+            let (quest, examples) = owners[index];
+            let (report, output, left) = &mut pending[quest];
+            if examples {
+                report.examples = reports;
+            } else {
+                report.parts.extend(reports);
+            }
+            output.extend(lines);
+            *left -= 1;
+            flush(&pending);
+        },
+    );
+    pending.into_iter().map(|(report, ..)| report).collect()
+}
+
+/// Prints the quest title and groups the selected parts by their input,
+/// the parsed input is shared by the parts of a group
+fn plan(
+    label: String,
+    quest: &Quest,
+    selected: &[usize],
+    out: &mut Output,
+) -> (QuestReport, Vec<Vec<Part>>) {
+    out.line(String::new());
+    let title = quest.info.title();
+    out.line(title.clone());
+    let mut report = QuestReport {
+        event: quest.info.event,
+        quest: quest.info.number,
+        label,
+        title,
+        parts: Vec::new(),
+        examples: Vec::new(),
+    };
+    let parts = quest.solution.parts();
+    for number in selected
+        .iter()
+        .filter(|n| parts.iter().all(|p| p.number != **n))
+    {
+        let err = SolveError::new(format!("part {} is not declared by the quest", number));
+        out.line(format!("[Error] {}", err));
+        report.parts.push(PartReport {
+            part: *number,
+            outcome: Outcome::Failed(err),
+            verdict: Verdict::Unknown,
+            parse: None,
+            duration: Duration::ZERO,
+            cached: false,
+            cache_key: None,
+            diagnostics: Diagnostics::default(),
+        });
+    }
+    let mut groups = Vec::<(usize, Vec<Part>)>::new();
+    for part in parts {
+        if !selected.is_empty() && !selected.contains(&part.number) {
+            continue;
+        }
+        let index = quest.input_loader.input_index(part);
+        match groups.iter_mut().find(|(i, _)| *i == index) {
+            Some((_, group)) => group.push(*part),
+            None => groups.push((index, vec![*part])),
+        }
+    }
+    let groups = groups.into_iter().map(|(_, group)| group).collect();
+    (report, groups)
+}
+
+/// Solves parts reading the same input, the input is loaded and parsed once.
+/// Cached answers of the input are served without solving
+fn solve_group(
+    quest: &Quest,
+    parts: &[Part],
+    answers: &[Option<String>],
+    config: &RunConfig,
+    cache: Option<&AnswerCache>,
+    out: &mut Output,
+) -> Vec<PartReport> {
+    let mut reports = Vec::new();
+    let mut input = None;
+    let mut cache_key = None;
+    let mut parsed_input = None;
+    for part in parts {
+        let name = format!("part {}", part.number);
+        if input.is_none() {
+            match quest.input_loader.load(part) {
+                Ok(text) => {
+                    cache_key = cache.map(|_| cache::input_key(quest.source, &text));
+                    input = Some(text);
+                }
+                Err(err) => {
+                    out.line(format!("[Error] {}", err));
+                    reports.push(PartReport {
+                        part: part.number,
+                        outcome: Outcome::NoInput(err),
+                        verdict: Verdict::Unknown,
+                        parse: None,
+                        duration: Duration::ZERO,
+                        cached: false,
+                        cache_key: None,
+                        diagnostics: Diagnostics::default(),
+                    });
+                    continue;
+                }
+            }
+        }
+        let expected = answers.get(part.index()).and_then(|s| s.as_deref());
+        let entry = cache.zip(cache_key).and_then(|(cache, key)| {
+            cache.get((quest.info.event, quest.info.number, part.number), key)
+        });
+        if let Some(entry) = entry {
+            reports.push(cached_part(part.number, entry, expected, &name, out));
+            continue;
+        }
+        let context = phase_context(&name, out);
+        let (parsed, parse) = match &parsed_input {
+            Some(parsed) => (Arc::clone(parsed), None),
+            None => {
+                let text = input.clone().unwrap_or_default();
+                match parse_input(quest, text, config, &name, &context, out) {
+                    Ok((parsed, duration)) => {
+                        parsed_input = Some(parsed.clone());
+                        (parsed, Some(duration))
+                    }
+                    Err(failure) => {
+                        reports.push(parse_failure(part.number, failure, &context));
+                        continue;
+                    }
+                }
+            }
+        };
+        let mut report = solve_part(
+            quest,
+            part.number,
+            (parsed, parse, context),
+            expected,
+            config,
+            &name,
+            out,
+        );
+        report.cache_key = cache_key;
+        reports.push(report);
+    }
+    reports
+}
+
+/// Report of the part answered from the cache, the duration is taken from the cached run
+fn cached_part(
+    part: usize,
+    entry: &Entry,
+    expected: Option<&str>,
+    name: &str,
+    out: &mut Output,
+) -> PartReport {
+    let answer = cache::restore_answer(&entry.answer);
+    let verdict = Verdict::check(&answer, expected);
+    let title = format!("{} ms for {}", entry.duration.as_millis(), name);
+    out.line(format!("{:>30}: {} [{}] (cached)", title, answer, verdict));
+    PartReport {
+        part,
+        outcome: Outcome::Solved(answer),
+        verdict,
+        parse: None,
+        duration: entry.duration,
+        cached: true,
+        cache_key: Some(entry.key),
+        diagnostics: Diagnostics::default(),
+    }
+}
+
+/// Solves the worked examples declared by the quest before its real input
+fn check_examples(quest: &Quest, config: &RunConfig, out: &mut Output) -> Vec<PartReport> {
+    let examples = quest
+        .examples
+        .iter()
+        .filter(|e| config.part.is_none_or(|p| p == e.part))
+        .collect::<Vec<_>>();
+    if examples.is_empty() {
+        out.line("[Warn] no examples declared".to_string());
+        return Vec::new();
+    }
+    out.line("Examples:".to_string());
+    let mut reports = Vec::new();
+    for (i, example) in examples.iter().enumerate() {
+        // examples are numbered within their part
+        let number = 1 + examples[..i]
+            .iter()
+            .filter(|e| e.part == example.part)
+            .count();
+        let name = format!("part {} example {}", example.part, number);
+        let input = example.input.to_string();
+        let context = phase_context(&name, out);
+        let report = match parse_input(quest, input, config, &name, &context, out) {
+            Ok((parsed, parse)) => solve_part(
+                quest,
+                example.part,
+                (parsed, Some(parse), context),
+                Some(example.expected),
+                config,
+                &name,
+                out,
+            ),
+            Err(failure) => parse_failure(example.part, failure, &context),
+        };
+        reports.push(report);
+    }
+    let passed = reports.iter().filter(|r| !r.is_failure()).count();
+    out.line(format!("{}/{} examples passed", passed, reports.len()));
+    reports
+}
+
+/// Solves the part for the given input instead of the input file, the answer isn't checked
+pub fn solve_text(quest: &Quest, part: usize, input: String, config: &RunConfig) -> PartReport {
+    let mut out = Output::console(config.format);
+    let name = format!("part {}", part);
+    let context = phase_context(&name, &out);
+    match parse_input(quest, input, config, &name, &context, &mut out) {
+        Ok((parsed, parse)) => solve_part(
+            quest,
+            part,
+            (parsed, Some(parse), context),
+            None,
+            config,
+            &name,
+            &mut out,
+        ),
+        Err(failure) => parse_failure(part, failure, &context),
+    }
+}
+
+/// Context of the part phases, its progress is shown on the console
+fn phase_context(name: &str, out: &Output) -> Arc<SolveContext> {
+    if !out.shows_progress() {
+        return Arc::new(SolveContext::new());
+    }
+    let name = name.to_string();
+    Arc::new(SolveContext::with_progress(move |progress| {
+        eprint!("\r{:>30}: {}", name, progress);
+    }))
+}
+
+/// Parses the input of the part in a worker thread
+fn parse_input(
+    quest: &Quest,
+    input: String,
+    config: &RunConfig,
+    name: &str,
+    context: &Arc<SolveContext>,
+    out: &mut Output,
+) -> Result<(ParsedInput, Duration), (Outcome, Duration)> {
+    let solution = quest.solution.clone();
+    let phase = format!("{} parse", name);
+    run_phase(&phase, config.timeout, context, out, move || {
+        solution.parse(&input)
+    })
+    .inspect_err(|(outcome, duration)| {
+        print_failure(&phase, outcome, *duration, out);
+        print_diagnostics(&context.diagnostics(), out);
+    })
+}
+
+/// Report of the part whose input failed to parse
+fn parse_failure(
+    part: usize,
+    (outcome, duration): (Outcome, Duration),
+    context: &SolveContext,
+) -> PartReport {
+    PartReport {
+        part,
+        outcome,
+        verdict: Verdict::Unknown,
+        parse: Some(duration),
+        duration: Duration::ZERO,
+        cached: false,
+        cache_key: None,
+        diagnostics: context.diagnostics(),
+    }
+}
+
+/// Solves the part in a worker thread within the time left after parsing
+/// and checks the answer if it's known. The parse time is `None` for reused input,
+/// the context is shared with the parse phase of the part
+fn solve_part(
+    quest: &Quest,
+    part: usize,
+    (parsed, parse, context): (ParsedInput, Option<Duration>, Arc<SolveContext>),
+    expected: Option<&str>,
+    config: &RunConfig,
+    name: &str,
+    out: &mut Output,
+) -> PartReport {
+    let solution = quest.solution.clone();
+    let timeout = config
+        .timeout
+        .map(|t| t.saturating_sub(parse.unwrap_or_default()));
+    let solved = run_phase(name, timeout, &context, out, move || {
+        solution.solve_parsed(part - 1, &parsed)
+    });
+    let (outcome, verdict, duration) = match solved {
+        Ok((answer, duration)) => {
+            let verdict = Verdict::check(&answer, expected);
+            let title = format!("{} ms for {}", duration.as_millis(), name);
+            let parse = parse
+                .map(|d| format!(" (parse {})", format_duration(d)))
+                .unwrap_or_default();
+            out.line(format!("{:>30}: {} [{}]{}", title, answer, verdict, parse));
+            (Outcome::Solved(answer), verdict, duration)
+        }
+        Err((outcome, duration)) => {
+            print_failure(name, &outcome, duration + parse.unwrap_or_default(), out);
+            (outcome, Verdict::Unknown, duration)
+        }
+    };
+    let diagnostics = context.diagnostics();
+    print_diagnostics(&diagnostics, out);
+    PartReport {
+        part,
+        outcome,
+        verdict,
+        parse,
+        duration,
+        cached: false,
+        cache_key: None,
+        diagnostics,
+    }
+}
+
+/// Runs a phase of the part in a worker thread, failures are turned into the part outcome
+fn run_phase<T, F>(
+    name: &str,
+    timeout: Option<Duration>,
+    context: &Arc<SolveContext>,
+    out: &mut Output,
+    f: F,
+) -> Result<(T, Duration), (Outcome, Duration)>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, SolveError> + Send + 'static,
+{
+    let run = worker::run_isolated(name.to_string(), timeout, context.clone(), f);
+    if context.progress_shown() {
+        // clears the progress line
+        eprint!("\r\x1b[K");
+    }
+    match run {
+        PartRun::Finished {
+            result: Ok(Ok(value)),
+            duration,
+        } => Ok((value, duration)),
+        PartRun::Finished {
+            result: Ok(Err(err)),
+            duration,
+        } => Err((Outcome::Failed(err), duration)),
+        PartRun::Finished {
+            result: Err(report),
+            duration,
+        } => Err((Outcome::Panicked(report), duration)),
+        PartRun::TimedOut { stopped } => {
+            if !stopped {
+                out.line(format!("[Warn] {} didn't stop after cancellation", name));
+            }
+            Err((Outcome::Timeout, timeout.unwrap_or_default()))
+        }
+    }
+}
+
+/// Messages and counters, indented under the part result
+fn print_diagnostics(diagnostics: &Diagnostics, out: &mut Output) {
+    for entry in &diagnostics.logs {
+        out.line(format!("{:>32}[{}] {}", "", entry.level, entry.message));
+    }
+    if diagnostics.dropped > 0 {
+        out.line(format!("{:>32}{} more messages", "", diagnostics.dropped));
+    }
+    if !diagnostics.counters.is_empty() {
+        let counters = diagnostics
+            .counters
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>();
+        out.line(format!("{:>32}counters: {}", "", counters.join(", ")));
+    }
+}
+
+fn print_failure(name: &str, outcome: &Outcome, elapsed: Duration, out: &mut Output) {
+    let title = format!("{} ms for {}", elapsed.as_millis(), name);
+    match outcome {
+        Outcome::Timeout => out.line(format!("{:>30}: [TIMEOUT]", title)),
+        Outcome::Failed(err) => out.line(format!("{:>30}: [Error] {}", title, err)),
+        Outcome::Panicked(report) => out.line(format!("{:>30}: [Error] {}", title, report)),
+        _ => {}
+    }
+}
+
+pub fn print_summary(reports: &[QuestReport]) {
+    // columns for every part number met in the reports
+    let part_count = reports
+        .iter()
+        .flat_map(|r| r.parts.iter().map(|p| p.part))
+        .max()
+        .unwrap_or_default();
+    let mut header = vec!["Quest".to_string(), "Title".to_string()];
+    header.extend((1..=part_count).map(|part| format!("Part {}", part)));
+    header.push("Status".to_string());
+    header.push("Parse".to_string());
+    header.extend((1..=part_count).map(|part| format!("P{} time", part)));
+    header.push("Total".to_string());
+
+    let mut rows = Vec::new();
+    for report in reports {
+        let mut row = vec![report.label.clone(), truncated(&report.title, 40)];
+        for part in 1..=part_count {
+            let answer = report
+                .part(part)
+                .map(|p| p.summary())
+                .unwrap_or("-".to_string());
+            row.push(truncated(&answer, 20));
+        }
+        row.push(report.status());
+        row.push(format_duration(report.parse_total()));
+        for part in 1..=part_count {
+            let time = report
+                .part(part)
+                .filter(|p| !matches!(p.outcome, Outcome::NoInput(_)))
+                .map(|p| format_duration(p.duration))
+                .unwrap_or("-".to_string());
+            row.push(time);
+        }
+        row.push(format_duration(report.total()));
+        rows.push(row);
+    }
+    let total = reports.iter().map(|r| r.total()).sum::<Duration>();
+    let mut footer = vec![String::new(); header.len()];
+    footer[1] = "Total".to_string();
+    footer[header.len() - 1] = format_duration(total);
+
+    let widths = (0..header.len())
+        .map(|i| {
+            std::iter::once(&header)
+                .chain(rows.iter())
+                .chain(std::iter::once(&footer))
+                .map(|row| row[i].chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+    let separator = widths
+        .iter()
+        .map(|w| "-".repeat(*w))
+        .collect::<Vec<_>>()
+        .join("-+-");
+
+    println!();
+    // durations follow the status column, starting with the parse time
+    let first_duration = part_count + 3;
+    print_row(&header, &widths, first_duration);
+    println!("{}", separator);
+    for row in &rows {
+        print_row(row, &widths, first_duration);
+    }
+    println!("{}", separator);
+    print_row(&footer, &widths, first_duration);
+}
+
+fn print_row(row: &[String], widths: &[usize], first_duration: usize) {
+    let line = row
+        .iter()
+        .zip(widths)
+        .enumerate()
+        .map(|(i, (value, width))| {
+            // durations are right aligned, text is left aligned
+            if i >= first_duration {
+                format!("{:>width$}", value, width = width)
+            } else {
+                format!("{:<width$}", value, width = width)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" | ");
+    println!("{}", line.trim_end());
+}
+
+fn truncated(value: &str, max_len: usize) -> String {
+    if value.chars().count() <= max_len {
+        return value.to_string();
+    }
+    let mut result = value.chars().take(max_len - 1).collect::<String>();
+    result.push('…');
+    result
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::quests::{Event, Part, QuestInfo, QuestInputLoader, Solution, SolveResult};
+    use std::sync::Arc;
+
+    const SHARED_PARTS: &[Part] = &[
+        Part::new(1),
+        Part {
+            number: 2,
+            input: 1,
+        },
+    ];
+
+    struct SharedInput;
+
+    impl Solution for SharedInput {
+        fn parts(&self) -> &'static [Part] {
+            SHARED_PARTS
+        }
+
+        fn part_one(&self, input: &str) -> SolveResult {
+            Ok(input.len().into())
+        }
+
+        fn part_two(&self, input: &str) -> SolveResult {
+            Ok(input.lines().count().into())
+        }
+    }
+
+    fn shared_input_quest(path: &std::path::Path) -> Quest {
+        Quest {
+            info: QuestInfo {
+                event: Event::Year(2024),
+                number: 99,
+                name: "Shared input",
+                tags: &[],
+                difficulty: None,
+            },
+            input_loader: QuestInputLoader::with_sources(&[path.display().to_string()]),
+            solution: Arc::new(SharedInput),
+            examples: &[],
+            source: "",
+        }
+    }
+
+    /// Temporary file unique to the test and the process, tests run in parallel
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("koa_runner_{}_{}", std::process::id(), name))
+    }
+
+    fn answers(report: &QuestReport) -> Vec<String> {
+        report
+            .parts
+            .iter()
+            .map(|p| match &p.outcome {
+                Outcome::Solved(answer) => answer.to_string(),
+                _ => panic!("part {} must be solved", p.part),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn runner_declared_parts() {
+        let path = temp_path("declared_parts.txt");
+        std::fs::write(&path, "ab\ncd").unwrap();
+        let quest = shared_input_quest(&path);
+        let report = execute(String::new(), &quest, &RunConfig::default(), None);
+        assert_eq!(answers(&report), ["5", "2"]);
+        // the second part reuses the input parsed for the first one
+        assert!(report.parts[0].parse.is_some());
+        assert!(report.parts[1].parse.is_none());
+
+        let config = RunConfig {
+            part: Some(3),
+            ..Default::default()
+        };
+        let report = execute(String::new(), &quest, &config, None);
+        assert!(report.has_failures());
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn runner_missing_input() {
+        let path = temp_path("missing_input.txt");
+        std::fs::remove_file(&path).ok();
+        let quest = shared_input_quest(&path);
+        let report = execute(String::new(), &quest, &RunConfig::default(), None);
+        assert!(report.parts.iter().all(|p| p.status() == "NO_INPUT"));
+        assert_eq!(report.status(), "2 NO_INPUT");
+        assert!(report.has_failures());
+    }
+
+    #[test]
+    fn runner_answer_cache() {
+        let path = temp_path("answer_cache.txt");
+        std::fs::write(&path, "ab\ncd").unwrap();
+        let quest = shared_input_quest(&path);
+        let mut cache = AnswerCache::default();
+        let entry = Entry {
+            key: cache::input_key(quest.source, "ab\ncd"),
+            answer: "42".to_string(),
+            duration: Duration::from_millis(5),
+        };
+        cache.insert((quest.info.event, quest.info.number, 1), entry);
+        let report = execute(String::new(), &quest, &RunConfig::default(), Some(&cache));
+        assert_eq!(answers(&report), ["42", "2"]);
+        assert!(report.parts[0].cached);
+        assert!(!report.parts[1].cached);
+
+        update_cache(&mut cache, std::slice::from_ref(&report));
+        let key = report.parts[1].cache_key.unwrap();
+        assert!(cache
+            .get((quest.info.event, quest.info.number, 2), key)
+            .is_some());
+
+        // changed input is solved again
+        std::fs::write(&path, "ab\ncde").unwrap();
+        let report = execute(String::new(), &quest, &RunConfig::default(), Some(&cache));
+        assert_eq!(answers(&report), ["6", "2"]);
+        assert!(report.parts.iter().all(|p| !p.cached));
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn runner_execute_parallel() {
+        let paths = ["ab\ncd", "abc\nd\ne"].map(|content| {
+            let path = temp_path(&format!("execute_parallel_{}.txt", content.len()));
+            std::fs::write(&path, content).unwrap();
+            path
+        });
+        let quests = paths
+            .iter()
+            .enumerate()
+            .map(|(i, path)| (i.to_string(), shared_input_quest(path)))
+            .collect::<Vec<_>>();
+        // the answer cache file must not be touched regardless of KOA_CACHE
+        let config = RunConfig {
+            jobs: 4,
+            cache: Some(false),
+            ..Default::default()
+        };
+        let reports = execute_all(&quests, &config);
+        let labels = reports.iter().map(|r| r.label.as_str()).collect::<Vec<_>>();
+        assert_eq!(labels, ["0", "1"]);
+        assert_eq!(answers(&reports[0]), ["5", "2"]);
+        assert_eq!(answers(&reports[1]), ["7", "3"]);
+        for path in paths {
+            std::fs::remove_file(path).ok();
+        }
+    }
+
+    #[test]
+    fn runner_truncated() {
+        assert_eq!(truncated("abc", 3), "abc");
+        assert_eq!(truncated("abcdef", 4), "abc…");
+    }
+
+    #[test]
+    fn runner_verdict() {
+        let fail = |mismatch| Verdict::Fail {
+            expected: "42".to_string(),
+            mismatch,
+        };
+        assert_eq!(Verdict::check(&Answer::Int(42), Some("42")), Verdict::Pass);
+        assert_eq!(
+            Verdict::check(&Answer::BigInt(42), Some("42")),
+            Verdict::Pass
+        );
+        assert_eq!(Verdict::check(&"42".into(), Some("42")), Verdict::Pass);
+        assert_eq!(Verdict::check(&Answer::Int(42), None), Verdict::Unknown);
+        assert_eq!(
+            Verdict::check(&Answer::Int(41), Some("42")),
+            fail(Mismatch::Value)
+        );
+        assert_eq!(
+            Verdict::check(&"ABC".into(), Some("42")),
+            fail(Mismatch::Type { actual: "text" })
+        );
+        assert_eq!(
+            Verdict::check(&Answer::NotImplemented, Some("42")),
+            fail(Mismatch::Type {
+                actual: "not implemented"
+            })
+        );
+    }
+
+    #[test]
+    fn runner_format_duration() {
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.500 ms");
+    }
+}
@@ -0,0 +1,180 @@
+use crate::quests::event::{self, Event};
+use std::path::{Path, PathBuf};
+
+const QUESTS_DIR: &str = "src/quests";
+
+const INPUT_DIR: &str = "input";
+
+const ASSEMBLERS_DECL: &str = "const ASSEMBLERS: &[fn() -> Quest] = &[";
+
+/// Generates the quest module from the template, registers it in the quest factory
+/// and creates empty input files. Returns paths of the created files
+pub fn create(
+    root: &Path,
+    event: Event,
+    number: usize,
+    title: &str,
+) -> Result<Vec<PathBuf>, String> {
+    // file naming conventions are declared per event
+    if event::spec(event).is_none() {
+        return Err(format!("unknown event {}", event));
+    }
+    let module_path = root.join(module_path(event, number));
+    if module_path.exists() {
+        return Err(format!("{} already exists", module_path.display()));
+    }
+    let registry_path = root.join(QUESTS_DIR).join("mod.rs");
+    let registry = std::fs::read_to_string(&registry_path)
+        .map_err(|err| format!("failed to read {}: {}", registry_path.display(), err))?;
+    let registry = register(&registry, event, number)?;
+
+    write(&module_path, &render_module(event, number, title))?;
+    if let Err(err) = write(&registry_path, &registry) {
+        // an unregistered module would be left behind
+        std::fs::remove_file(&module_path).ok();
+        return Err(err);
+    }
+    let mut created = vec![module_path];
+    for part in 1..=3 {
+        let path = root
+            .join(INPUT_DIR)
+            .join(event::input_file_name(event, number, part));
+        // inputs may be downloaded before the quest is scaffolded
+        if path.exists() {
+            continue;
+        }
+        write(&path, "")?;
+        created.push(path);
+    }
+    Ok(created)
+}
+
+fn write(path: &Path, content: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .map_err(|err| format!("failed to create {}: {}", dir.display(), err))?;
+    }
+    std::fs::write(path, content)
+        .map_err(|err| format!("failed to write {}: {}", path.display(), err))
+}
+
+fn module_name(event: Event, number: usize) -> String {
+    format!("quest_{}_{:02}", event, number)
+}
+
+/// Path of the quest module relative to the crate root
+pub fn module_path(event: Event, number: usize) -> PathBuf {
+    Path::new(QUESTS_DIR).join(format!("{}.rs", module_name(event, number)))
+}
+
+/// Same template as the `qqnewpuzzle` snippet in `.vscode/rust.code-snippets`
+fn render_module(event: Event, number: usize, title: &str) -> String {
+    let struct_name = format!("Q{}_{}", event.to_string().to_uppercase(), number);
+    let name = title.replace('\\', "\\\\").replace('"', "\\\"");
+    format!(
+        r#"use std::sync::Arc;
+
+use crate::quests::{{Event, Example, Quest, QuestInfo, QuestInputLoader, Solution}};
+
+const INFO: QuestInfo = QuestInfo {{
+    event: Event::{event:?},
+    number: {number},
+    name: "{name}",
+    tags: &[],
+    difficulty: None,
+}};
+
+pub fn assemble() -> Quest {{
+    Quest {{
+        info: INFO,
+        input_loader: QuestInputLoader::with_quest_id(INFO.event, INFO.number),
+        solution: Arc::new({struct_name}),
+        examples: EXAMPLES,
+        source: crate::quest_source!(),
+    }}
+}}
+
+const EXAMPLES: &[Example] = &[];
+
+struct {struct_name};
+
+// parts are reported as not implemented until they are defined
+impl Solution for {struct_name} {{}}
+"#
+    )
+}
+
+/// Adds the module declaration and the factory entry to the source of `quests/mod.rs`
+fn register(source: &str, event: Event, number: usize) -> Result<String, String> {
+    let module = module_name(event, number);
+    let declaration = format!("mod {};", module);
+    let mut lines = source.lines().map(|s| s.to_string()).collect::<Vec<_>>();
+    if lines.contains(&declaration) {
+        return Err(format!("quest {} {} is already registered", event, number));
+    }
+
+    let decl_index = lines
+        .iter()
+        .position(|line| line == ASSEMBLERS_DECL)
+        .ok_or("quest registry declaration not found")?;
+    let end_index = lines[decl_index..]
+        .iter()
+        .position(|line| line.trim() == "];")
+        .map(|i| decl_index + i)
+        .ok_or("end of quest registry declaration not found")?;
+    lines.insert(end_index, format!("    {}::assemble,", module));
+
+    // declarations are kept sorted like rustfmt does
+    let last_index = lines
+        .iter()
+        .rposition(|line| line.starts_with("mod quest_"))
+        .ok_or("quest module declarations not found")?;
+    let mod_index = lines[..=last_index]
+        .iter()
+        .position(|line| line.starts_with("mod quest_") && *line > declaration)
+        .unwrap_or(last_index + 1);
+    lines.insert(mod_index, declaration);
+
+    let mut output = lines.join("\n");
+    output.push('\n');
+    Ok(output)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scaffold_register() {
+        let source = "mod answer;
+mod quest_2024_01;
+
+const ASSEMBLERS: &[fn() -> Quest] = &[
+    quest_2024_01::assemble,
+];
+";
+        let expected = "mod answer;
+mod quest_2024_01;
+mod quest_s1_02;
+
+const ASSEMBLERS: &[fn() -> Quest] = &[
+    quest_2024_01::assemble,
+    quest_s1_02::assemble,
+];
+";
+        assert_eq!(register(source, Event::Story(1), 2).unwrap(), expected);
+        assert!(register(source, Event::Year(2024), 1).is_err());
+        let registered = register(expected, Event::Year(2024), 2).unwrap();
+        assert!(registered.contains("mod quest_2024_02;\nmod quest_s1_02;"));
+        assert!(register("mod quest_2024_01;", Event::Year(2024), 2).is_err());
+    }
+
+    #[test]
+    fn scaffold_render_module() {
+        let module = render_module(Event::Story(1), 8, "A \"Quoted\" Title");
+        assert!(module.contains("name: \"A \\\"Quoted\\\" Title\","));
+        assert!(module.contains("event: Event::Story(1),"));
+        assert!(module.contains("impl Solution for QS1_8 {}"));
+        assert!(!module.contains("todo!()"));
+    }
+}
@@ -0,0 +1,184 @@
+use crate::quests::Quest;
+use crate::runner::{self, QuestReport, RunConfig};
+use crate::scaffold;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+pub const DEFAULT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Modification times of the watched files, `None` if the file is missing
+type Snapshot = HashMap<PathBuf, Option<SystemTime>>;
+
+/// Solves the quest and polls its input files, parts reading a changed file are solved again
+/// and their answers are compared with the previous ones. Runs until the process is stopped
+pub fn watch(
+    label: String,
+    quest: &Quest,
+    config: &RunConfig,
+    interval: Duration,
+) -> Result<(), String> {
+    let watched = quest
+        .solution
+        .parts()
+        .iter()
+        .filter(|part| config.part.is_none_or(|n| n == part.number))
+        .filter_map(|part| {
+            let path = quest.input_loader.input_path(part)?;
+            Some((part.number, path))
+        })
+        .collect::<Vec<_>>();
+    if watched.is_empty() {
+        return Err("quest has no input files to watch".to_string());
+    }
+    // solutions are compiled in, source changes only take effect after rebuild
+    let source = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join(scaffold::module_path(quest.info.event, quest.info.number));
+    let paths = watched
+        .iter()
+        .map(|(_, path)| path.clone())
+        .chain(std::iter::once(source.clone()))
+        .collect::<Vec<_>>();
+    let mut modified = snapshot(&paths);
+
+    let report = runner::execute_parts(label.clone(), quest, config, config.part.as_slice(), None);
+    let mut answers = summaries(&report);
+    println!();
+    println!(
+        "Watching {} input file(s), press Ctrl+C to stop",
+        modified.len() - 1
+    );
+    loop {
+        thread::sleep(interval);
+        let current = snapshot(&paths);
+        let changed = changed(&modified, &current);
+        modified = current;
+        if changed.contains(&source) {
+            println!(
+                "[Warn] {} changed, rebuild and restart to apply",
+                source.display()
+            );
+        }
+        let selected = watched
+            .iter()
+            .filter(|(_, path)| changed.contains(path))
+            .map(|(part, _)| *part)
+            .collect::<Vec<_>>();
+        if selected.is_empty() {
+            continue;
+        }
+        let report = runner::execute_parts(label.clone(), quest, config, &selected, None);
+        println!("Changes:");
+        for line in diff(&answers, &report) {
+            println!("  {}", line);
+        }
+        answers.extend(summaries(&report));
+    }
+}
+
+fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    paths
+        .iter()
+        .map(|path| {
+            let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+            (path.clone(), modified)
+        })
+        .collect()
+}
+
+/// Files created, removed or modified since the previous snapshot
+fn changed(previous: &Snapshot, current: &Snapshot) -> Vec<PathBuf> {
+    current
+        .iter()
+        .filter(|(path, modified)| previous.get(*path) != Some(modified))
+        .map(|(path, _)| path.clone())
+        .collect()
+}
+
+fn summaries(report: &QuestReport) -> HashMap<usize, String> {
+    report
+        .parts
+        .iter()
+        .map(|part| (part.part, part.summary()))
+        .collect()
+}
+
+/// One line per solved part with the previous and the current answer
+fn diff(previous: &HashMap<usize, String>, report: &QuestReport) -> Vec<String> {
+    report
+        .parts
+        .iter()
+        .map(|part| {
+            let current = part.summary();
+            let status = part.status();
+            match previous.get(&part.part) {
+                Some(old) if *old == current => {
+                    format!("part {}: {} [{}] (unchanged)", part.part, current, status)
+                }
+                Some(old) => format!("part {}: {} -> {} [{}]", part.part, old, current, status),
+                None => format!("part {}: {} [{}] (new)", part.part, current, status),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::quests::context::Diagnostics;
+    use crate::quests::{Answer, Event};
+    use crate::runner::{Outcome, PartReport, Verdict};
+
+    fn part(part: usize, answer: i64) -> PartReport {
+        PartReport {
+            part,
+            outcome: Outcome::Solved(Answer::Int(answer)),
+            verdict: Verdict::Unknown,
+            parse: None,
+            duration: Duration::ZERO,
+            cached: false,
+            cache_key: None,
+            diagnostics: Diagnostics::default(),
+        }
+    }
+
+    #[test]
+    fn watch_changed() {
+        let time = SystemTime::UNIX_EPOCH + Duration::from_secs(100);
+        let previous = Snapshot::from([
+            (PathBuf::from("a"), Some(time)),
+            (PathBuf::from("b"), Some(time)),
+            (PathBuf::from("c"), None),
+        ]);
+        let current = Snapshot::from([
+            (PathBuf::from("a"), Some(time)),
+            (PathBuf::from("b"), Some(time + Duration::from_secs(1))),
+            (PathBuf::from("c"), Some(time)),
+        ]);
+        let mut changed = changed(&previous, &current);
+        changed.sort();
+        assert_eq!(changed, [PathBuf::from("b"), PathBuf::from("c")]);
+    }
+
+    #[test]
+    fn watch_diff() {
+        let report = QuestReport {
+            event: Event::Year(2024),
+            quest: 1,
+            label: String::new(),
+            title: String::new(),
+            parts: vec![part(1, 10), part(2, 21), part(3, 30)],
+            examples: Vec::new(),
+        };
+        let previous = HashMap::from([(1, "10".to_string()), (2, "20".to_string())]);
+        assert_eq!(
+            diff(&previous, &report),
+            [
+                "part 1: 10 [UNKNOWN] (unchanged)",
+                "part 2: 20 -> 21 [UNKNOWN]",
+                "part 3: 30 [UNKNOWN] (new)",
+            ]
+        );
+    }
+}
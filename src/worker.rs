@@ -0,0 +1,129 @@
+use crate::isolation::{catch_panic, PanicReport};
+use crate::quests::context::{self, SolveContext};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Time given to a cancelled part to stop before its worker is abandoned
+const CANCEL_GRACE_PERIOD: Duration = Duration::from_millis(500);
+
+pub enum PartRun<T> {
+    Finished {
+        result: Result<T, PanicReport>,
+        duration: Duration,
+    },
+    TimedOut {
+        stopped: bool,
+    },
+}
+
+/// Runs a phase of the part in a separate thread with the context, isolates panics
+/// and cancels it on timeout
+pub fn run_isolated<T, F>(
+    name: String,
+    timeout: Option<Duration>,
+    context: Arc<SolveContext>,
+    f: F,
+) -> PartRun<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let token = context.token().clone();
+    let (sender, receiver) = mpsc::channel();
+    let spawned = thread::Builder::new().name(name).spawn(move || {
+        let result = context::with_context(context, || {
+            catch_panic(|| {
+                let now = Instant::now();
+                let result = f();
+                (result, now.elapsed())
+            })
+        });
+        // receiver is gone if the part timed out
+        sender.send(result).ok();
+    });
+    if let Err(err) = spawned {
+        let report = PanicReport {
+            message: format!("failed to spawn worker thread: {}", err),
+            location: None,
+        };
+        return PartRun::Finished {
+            result: Err(report),
+            duration: Duration::ZERO,
+        };
+    }
+    let received = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout),
+        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+    match received {
+        Ok(Ok((result, duration))) => PartRun::Finished {
+            result: Ok(result),
+            duration,
+        },
+        Ok(Err(report)) => PartRun::Finished {
+            result: Err(report),
+            duration: Duration::ZERO,
+        },
+        Err(RecvTimeoutError::Timeout) => {
+            token.cancel();
+            let stopped = receiver.recv_timeout(CANCEL_GRACE_PERIOD).is_ok();
+            PartRun::TimedOut { stopped }
+        }
+        Err(RecvTimeoutError::Disconnected) => PartRun::Finished {
+            result: Err(PanicReport {
+                message: "worker thread terminated unexpectedly".to_string(),
+                location: None,
+            }),
+            duration: Duration::ZERO,
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::quests::{Answer, Solution, SolveResult};
+
+    struct Endless;
+
+    impl Solution for Endless {
+        fn part_one(&self, input: &str) -> SolveResult {
+            Ok(input.into())
+        }
+
+        fn part_two(&self, _input: &str) -> SolveResult {
+            loop {
+                context::check_cancelled()?;
+                thread::sleep(Duration::from_millis(1));
+            }
+        }
+    }
+
+    #[test]
+    fn worker_run_isolated() {
+        let solution = Arc::new(Endless);
+        let worker = solution.clone();
+        let context = Arc::new(SolveContext::new());
+        let run = run_isolated("part-1".to_string(), None, context, move || {
+            worker.solve(0, "42")
+        });
+        let PartRun::Finished {
+            result: Ok(Ok(answer)),
+            ..
+        } = run
+        else {
+            panic!("part one must be solved");
+        };
+        assert_eq!(answer, Answer::from("42"));
+
+        let timeout = Some(Duration::from_millis(20));
+        let context = Arc::new(SolveContext::new());
+        let run = run_isolated("part-2".to_string(), timeout, context.clone(), move || {
+            solution.solve(1, "")
+        });
+        assert!(matches!(run, PartRun::TimedOut { stopped: true }));
+        assert!(context.token().is_cancelled());
+    }
+}